@@ -0,0 +1,320 @@
+use std::path::PathBuf;
+
+use chrono::{Days, NaiveDate};
+use chrono_tz::Tz;
+
+use bumbling::BumblingError;
+use bumbling::config::{self, Config};
+use bumbling::error::Result;
+use bumbling::generate::GenOptions;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Play,
+    Fetch,
+    Open,
+    Stats,
+    Generate,
+    Solve,
+    Hints,
+    Check,
+    Dates,
+    Help,
+}
+
+struct CommandInfo {
+    command: Command,
+    name: &'static str,
+    args: &'static str,
+    desc: &'static str,
+    // how many positional arguments it takes
+    min: usize,
+    max: usize,
+}
+
+const fn info(command: Command, name: &'static str, args: &'static str, desc: &'static str, min: usize, max: usize) -> CommandInfo {
+    CommandInfo { command, name, args, desc, min, max }
+}
+
+const COMMANDS: [CommandInfo; 10] = [
+    info(Command::Play, "play", "", "play today's puzzle (or --date), opening its save if there is one", 0, 0),
+    info(Command::Fetch, "fetch", "[url]", "download a puzzle and save it without playing", 0, 1),
+    info(Command::Open, "open", "<path>", "play a saved puzzle", 1, 1),
+    info(Command::Stats, "stats", "[path...]", "show progress in saved puzzles, every one in a directory (default .)", 0, usize::MAX),
+    info(Command::Generate, "generate", "<wordlist>", "make a puzzle from a word list, one word per line", 1, 1),
+    info(Command::Solve, "solve", "[path]", "print the answers of a saved puzzle (or --date), * marks pangrams, ** perfect ones", 0, 1),
+    info(Command::Hints, "hints", "[path]", "print the letter grid and two-letter list of what is left to find", 0, 1),
+    info(Command::Check, "check", "<path...>", "look for mistakes in .bumble files", 1, usize::MAX),
+    info(Command::Dates, "dates", "", "list the dates the puzzle source has", 0, 0),
+    info(Command::Help, "help", "[command]", "show this help", 0, 1),
+];
+
+fn find_command(name: &str) -> Option<&'static CommandInfo> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+fn command_info(command: Command) -> &'static CommandInfo {
+    COMMANDS.iter().find(|c| c.command == command).unwrap()
+}
+
+const OPTIONS: &str = "\
+options:
+  -d, --date <date>       puzzle date as YYYY-MM-DD, YYYYMMDD, today, yesterday or -N days
+  -o, --output <path>     where to write the save, defaults to YYYYMMDD.bumble
+      --source <source>   nytbee | select:<selector>[;<url with {date}>] | json:<path>
+      --tz <zone>         timezone whose midnight starts a new puzzle, default America/Los_Angeles
+      --center <letter>   center letter to use when the answers don't decide it,
+                          stats: only count puzzles with this center
+      --min <n>           generate: fewest answers (default 20)
+      --max <n>           generate: most answers (default 80)
+      --score <n>         generate: aim for this max score
+      --seed <n>          generate: the same puzzle every time for the same word list
+      --by <group>        solve: group answers by length (default) or letter
+      --unfound           solve: only list the words the save hasn't found
+      --csv               stats: one comma separated line per puzzle
+      --tui               play in the terminal instead of a window
+      --repl              read words from stdin, one per line, and answer OK or ERR
+  -h, --help              show help
+
+the old `_w <url> [path]`, `_s <url> [path]` and `_f <path>` forms still work but are deprecated.
+";
+
+// what to play in
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Frontend {
+    Sdl,
+    Tui,
+    Repl,
+}
+
+impl Default for Frontend {
+    // the window when there is one to open
+    fn default() -> Frontend {
+        if cfg!(feature = "sdl") {
+            Frontend::Sdl
+        } else {
+            Frontend::Tui
+        }
+    }
+}
+
+// how solve groups the answers
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Length,
+    Letter,
+}
+
+#[derive(Clone, Copy)]
+pub enum DateSpec {
+    Exact(NaiveDate),
+    DaysAgo(u64),
+}
+
+impl DateSpec {
+    pub fn resolve(self, today: NaiveDate) -> Result<NaiveDate> {
+        let date = match self {
+            DateSpec::Exact(d) => d,
+            DateSpec::DaysAgo(n) => today.checked_sub_days(Days::new(n))
+                .ok_or_else(|| usage_err(format!("{} days ago is out of range", n)))?,
+        };
+        if date > today {
+            return Err(usage_err(format!("there is no puzzle for {} yet", date)));
+        }
+        Ok(date)
+    }
+}
+
+pub struct Cli {
+    pub command: Command,
+    pub args: Vec<String>,
+    pub date: Option<DateSpec>,
+    pub output: Option<PathBuf>,
+    pub source: Option<String>,
+    pub center: Option<char>,
+    pub tz: Tz,
+    pub gen: GenOptions,
+    pub frontend: Frontend,
+    pub group: GroupBy,
+    pub unfound: bool,
+    pub csv: bool,
+}
+
+pub fn usage(command: Option<Command>) -> String {
+    let mut res = String::from("usage: bumbling [command] [options]\n\ncommands:\n");
+    for c in COMMANDS.iter() {
+        if command.is_none_or(|cmd| cmd == c.command || cmd == Command::Help) {
+            res.push_str(&format!("  {:<20}{}\n", format!("{} {}", c.name, c.args), c.desc));
+        }
+    }
+    res.push('\n');
+    res.push_str(OPTIONS);
+    res
+}
+
+fn usage_err(msg: impl std::fmt::Display) -> BumblingError {
+    BumblingError::Usage(format!("{}\nsee bumbling --help", msg))
+}
+
+// YYYY-MM-DD, YYYYMMDD, today, yesterday or -N for N days ago
+pub fn parse_date(s: &str) -> Result<DateSpec> {
+    match s {
+        "today" => return Ok(DateSpec::DaysAgo(0)),
+        "yesterday" => return Ok(DateSpec::DaysAgo(1)),
+        _ => {},
+    }
+    if let Some(n) = s.strip_prefix('-') {
+        if let Ok(n) = n.parse() {
+            return Ok(DateSpec::DaysAgo(n));
+        }
+    }
+
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(s, "%Y%m%d"))
+        .map(DateSpec::Exact)
+        .map_err(|_| usage_err(format!("bad date {}", s)))
+}
+
+fn parse_num(opt: &str, s: &str) -> Result<u32> {
+    s.parse().map_err(|_| usage_err(format!("{} expects a number, got {}", opt, s)))
+}
+
+pub fn parse(args: &[String], config: &Config) -> Result<Cli> {
+    let mut cli = Cli {
+        command: Command::Play,
+        args: Vec::new(),
+        date: None,
+        output: None,
+        source: None,
+        center: None,
+        tz: config.timezone,
+        gen: GenOptions::default(),
+        frontend: Frontend::default(),
+        group: GroupBy::Length,
+        unfound: false,
+        csv: false,
+    };
+    let mut command = None;
+    let mut help = false;
+
+    let mut argi = 0;
+    while argi < args.len() {
+        let arg = &args[argi];
+        argi += 1;
+
+        if !arg.starts_with('-') || arg == "-" {
+            if command.is_none() {
+                match find_command(arg) {
+                    Some(c) => {
+                        command = Some(c.command);
+                        continue;
+                    },
+                    None if cli.args.is_empty() => {
+                        return Err(usage_err(format!("unknown command {}", arg)));
+                    },
+                    None => {},
+                }
+            }
+            cli.args.push(arg.clone());
+            continue;
+        }
+
+        match arg.as_str() {
+            "-h" | "--help" => {
+                help = true;
+                continue;
+            },
+            "--tui" => {
+                cli.frontend = Frontend::Tui;
+                continue;
+            },
+            "--repl" => {
+                cli.frontend = Frontend::Repl;
+                continue;
+            },
+            "--unfound" => {
+                cli.unfound = true;
+                continue;
+            },
+            "--csv" => {
+                cli.csv = true;
+                continue;
+            },
+            _ => {},
+        }
+
+        // --opt=value or --opt value
+        let (opt, inline) = match arg.split_once('=') {
+            Some((o, v)) => (o, Some(v.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let val = match inline {
+            Some(v) => v,
+            None if argi < args.len() => {
+                argi += 1;
+                args[argi - 1].clone()
+            },
+            None => {
+                return Err(usage_err(format!("{} expects a value", opt)));
+            },
+        };
+
+        match opt {
+            "-d" | "--date" => cli.date = Some(parse_date(&val)?),
+            "-o" | "--output" => cli.output = Some(PathBuf::from(val)),
+            "--source" => cli.source = Some(val),
+            "--tz" => cli.tz = config::parse_timezone(&val)?,
+            "--center" => {
+                let c = val.to_ascii_lowercase();
+                cli.center = match c.chars().next() {
+                    Some(l) if c.len() == 1 && l.is_ascii_lowercase() => Some(l),
+                    _ => {
+                        return Err(usage_err(format!("--center expects a single letter, got {}", val)));
+                    },
+                };
+            },
+            "--min" => cli.gen.min_answers = parse_num(opt, &val)? as usize,
+            "--max" => cli.gen.max_answers = parse_num(opt, &val)? as usize,
+            "--score" => cli.gen.target_score = Some(parse_num(opt, &val)?),
+            "--seed" => {
+                cli.gen.seed = Some(val.parse().map_err(|_| usage_err(format!("{} expects a number, got {}", opt, val)))?);
+            },
+            "--by" => {
+                cli.group = match val.as_str() {
+                    "length" => GroupBy::Length,
+                    "letter" => GroupBy::Letter,
+                    _ => {
+                        return Err(usage_err(format!("--by expects length or letter, got {}", val)));
+                    },
+                };
+            },
+            _ => {
+                return Err(usage_err(format!("unknown option {}", opt)));
+            },
+        }
+    }
+
+    if help {
+        cli.args = command.map(|c| vec![command_info(c).name.to_string()]).unwrap_or_default();
+        cli.command = Command::Help;
+        return Ok(cli);
+    }
+    cli.command = command.unwrap_or(Command::Play);
+
+    let info = command_info(cli.command);
+    if cli.args.len() < info.min || cli.args.len() > info.max {
+        return Err(usage_err(format!("wrong number of arguments for {}", info.name)));
+    }
+
+    Ok(cli)
+}
+
+pub fn help_for(name: Option<&str>) -> Result<String> {
+    match name {
+        None => Ok(usage(None)),
+        Some(n) => match find_command(n) {
+            Some(c) => Ok(usage(Some(c.command))),
+            None => Err(usage_err(format!("unknown command {}", n))),
+        },
+    }
+}
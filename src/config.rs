@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+
+use chrono_tz::Tz;
+
+use crate::error::{BumblingError, Result};
+
+// the puzzle flips over at midnight where it is published
+pub const PUBLISHER_TZ: Tz = chrono_tz::US::Pacific;
+
+pub struct Config {
+    pub timezone: Tz,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { timezone: PUBLISHER_TZ }
+    }
+}
+
+// $BUMBLING_CONFIG, or bumbling/config under $XDG_CONFIG_HOME or ~/.config
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(p) = std::env::var_os("BUMBLING_CONFIG") {
+        return Some(PathBuf::from(p));
+    }
+    let dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(d) => PathBuf::from(d),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("bumbling").join("config"))
+}
+
+pub fn parse_timezone(s: &str) -> Result<Tz> {
+    s.parse().map_err(|_| BumblingError::Usage(format!("unknown timezone {}", s)))
+}
+
+// `key = value` lines, `#` starts a comment
+pub fn parse(src: &str, path: &Path) -> Result<Config> {
+    let mut config = Config::default();
+
+    for (n, line) in src.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let err = |msg: String| BumblingError::Usage(format!("{}:{}: {}", path.display(), n + 1, msg));
+
+        let (key, val) = match line.split_once('=') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => {
+                return Err(err(format!("expected key = value, got {}", line)));
+            },
+        };
+        match key {
+            "timezone" => config.timezone = parse_timezone(val).map_err(|e| err(e.to_string()))?,
+            _ => {
+                return Err(err(format!("unknown setting {}", key)));
+            },
+        }
+    }
+
+    Ok(config)
+}
+
+pub fn load() -> Result<Config> {
+    let path = match config_path() {
+        Some(p) => p,
+        None => {
+            return Ok(Config::default());
+        },
+    };
+
+    match std::fs::read_to_string(&path) {
+        Ok(src) => parse(&src, &path),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(BumblingError::SaveIo(path, e)),
+    }
+}
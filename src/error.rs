@@ -0,0 +1,65 @@
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum BumblingError {
+    Network(curl::Error),
+    HtmlParse(String),
+    InvalidPuzzle(String),
+    // the answers fit more than one center letter and nothing said which
+    AmbiguousCenter(Vec<char>),
+    SaveIo(PathBuf, std::io::Error),
+    Sdl(String),
+    Terminal(String),
+    Usage(String),
+}
+
+pub type Result<T> = std::result::Result<T, BumblingError>;
+
+impl BumblingError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            BumblingError::Network(_) => 2,
+            BumblingError::HtmlParse(_) => 3,
+            BumblingError::InvalidPuzzle(_) => 4,
+            BumblingError::AmbiguousCenter(_) => 7,
+            BumblingError::SaveIo(..) => 5,
+            BumblingError::Sdl(_) => 6,
+            BumblingError::Terminal(_) => 8,
+            BumblingError::Usage(_) => 1,
+        }
+    }
+}
+
+impl fmt::Display for BumblingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BumblingError::Network(e) => write!(f, "network error: {}", e),
+            BumblingError::HtmlParse(e) => write!(f, "could not parse page: {}", e),
+            BumblingError::InvalidPuzzle(e) => write!(f, "invalid puzzle: {}", e),
+            BumblingError::AmbiguousCenter(c) => {
+                write!(f, "could not determine center letter, one of [{}], use --center", c.iter().collect::<String>())
+            },
+            BumblingError::SaveIo(p, e) => write!(f, "could not access {}: {}", p.display(), e),
+            BumblingError::Sdl(e) => write!(f, "sdl error: {}", e),
+            BumblingError::Terminal(e) => write!(f, "terminal error: {}", e),
+            BumblingError::Usage(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for BumblingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BumblingError::Network(e) => Some(e),
+            BumblingError::SaveIo(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<curl::Error> for BumblingError {
+    fn from(e: curl::Error) -> Self {
+        BumblingError::Network(e)
+    }
+}
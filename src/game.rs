@@ -0,0 +1,319 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::puzzle::{lset, Puzzle};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rank {
+    Beginner,
+    GoodStart,
+    MovingUp,
+    Good,
+    Solid,
+    Nice,
+    Great,
+    Amazing,
+    Genius,
+    QueenBee,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 10] = [
+        Rank::Beginner,
+        Rank::GoodStart,
+        Rank::MovingUp,
+        Rank::Good,
+        Rank::Solid,
+        Rank::Nice,
+        Rank::Great,
+        Rank::Amazing,
+        Rank::Genius,
+        Rank::QueenBee,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rank::Beginner => "Beginner",
+            Rank::GoodStart => "Good Start",
+            Rank::MovingUp => "Moving Up",
+            Rank::Good => "Good",
+            Rank::Solid => "Solid",
+            Rank::Nice => "Nice",
+            Rank::Great => "Great",
+            Rank::Amazing => "Amazing",
+            Rank::Genius => "Genius",
+            Rank::QueenBee => "Queen Bee",
+        }
+    }
+
+    pub fn percent(self) -> u32 {
+        match self {
+            Rank::Beginner => 0,
+            Rank::GoodStart => 2,
+            Rank::MovingUp => 5,
+            Rank::Good => 8,
+            Rank::Solid => 15,
+            Rank::Nice => 25,
+            Rank::Great => 40,
+            Rank::Amazing => 50,
+            Rank::Genius => 70,
+            Rank::QueenBee => 100,
+        }
+    }
+
+    pub fn threshold(self, max_score: u32) -> u32 {
+        (max_score * self.percent() + 50) / 100
+    }
+
+    pub fn next(self) -> Option<Rank> {
+        Rank::ALL.get(self as usize + 1).copied()
+    }
+
+    pub fn for_score(score: u32, max_score: u32) -> Rank {
+        let mut rank = Rank::Beginner;
+        for r in Rank::ALL {
+            if score >= r.threshold(max_score) {
+                rank = r;
+            }
+        }
+        rank
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Submission {
+    TooShort,
+    BadLetter,
+    MissingCenter,
+    NotInList,
+    AlreadyFound,
+    // points scored
+    Accepted(u32),
+    Pangram(u32),
+}
+
+impl Submission {
+    pub const REJECTIONS: [Submission; 5] = [
+        Submission::TooShort,
+        Submission::BadLetter,
+        Submission::MissingCenter,
+        Submission::NotInList,
+        Submission::AlreadyFound,
+    ];
+
+    pub fn is_ok(self) -> bool {
+        matches!(self, Submission::Accepted(_) | Submission::Pangram(_))
+    }
+
+    // machine readable name, also used to count rejections in the save
+    pub fn key(self) -> &'static str {
+        match self {
+            Submission::TooShort => "too-short",
+            Submission::BadLetter => "bad-letter",
+            Submission::MissingCenter => "missing-center",
+            Submission::NotInList => "not-in-list",
+            Submission::AlreadyFound => "already-found",
+            Submission::Accepted(_) => "ok",
+            Submission::Pangram(_) => "pangram",
+        }
+    }
+
+    pub fn message(self) -> String {
+        match self {
+            Submission::TooShort => "Too short".to_string(),
+            Submission::BadLetter => "Bad letters".to_string(),
+            Submission::MissingCenter => "Missing center letter".to_string(),
+            Submission::NotInList => "Not in word list".to_string(),
+            Submission::AlreadyFound => "Already found".to_string(),
+            Submission::Accepted(pts) => format!("+{}", pts),
+            Submission::Pangram(pts) => format!("Pangram! +{}", pts),
+        }
+    }
+}
+
+pub fn shuffle_letters(letters: &mut [char; 7]) {
+    let mut r: u32 = rand::random();
+    for i in 1..7 {
+        let rn: usize = (6 - r % (7 - i)) as usize;
+        r /= 7 - i;
+        letters.swap(i as usize, rn);
+    }
+}
+
+pub struct Find {
+    pub word: String,
+    // legacy saves don't record when a word was found
+    pub at: Option<DateTime<Utc>>,
+}
+
+pub struct HintUse {
+    pub at: DateTime<Utc>,
+    pub what: String,
+}
+
+pub struct GameState {
+    pub puzzle: Puzzle,
+    // found words in the order they were found
+    pub found: Vec<Find>,
+    pub hints: Vec<HintUse>,
+    // rejected submissions by Submission::key
+    pub misses: BTreeMap<String, u32>,
+    pub created: DateTime<Utc>,
+}
+
+impl GameState {
+    pub fn new(puzzle: Puzzle) -> GameState {
+        GameState {
+            puzzle,
+            found: Vec::new(),
+            hints: Vec::new(),
+            misses: BTreeMap::new(),
+            created: Utc::now(),
+        }
+    }
+
+    pub fn is_found(&self, w: &str) -> bool {
+        self.found.iter().any(|f| f.word == w)
+    }
+
+    pub fn use_hint(&mut self, what: impl ToString) {
+        self.hints.push(HintUse { at: Utc::now(), what: what.to_string() });
+    }
+
+    pub fn check(&self, w: &str) -> Submission {
+        let p = &self.puzzle;
+        if w.len() < 4 {
+            Submission::TooShort
+        } else if lset(w) & !p.lset() != 0 {
+            Submission::BadLetter
+        } else if !w.contains(p.center()) {
+            Submission::MissingCenter
+        } else if self.is_found(w) {
+            Submission::AlreadyFound
+        } else if !p.is_answer(w) {
+            Submission::NotInList
+        } else if p.is_pangram(w) {
+            Submission::Pangram(p.score(w))
+        } else {
+            Submission::Accepted(p.score(w))
+        }
+    }
+
+    pub fn submit(&mut self, w: &str) -> Submission {
+        let res = self.check(w);
+        if res.is_ok() {
+            self.found.push(Find { word: w.to_string(), at: Some(Utc::now()) });
+        } else {
+            *self.misses.entry(res.key().to_string()).or_insert(0) += 1;
+        }
+        res
+    }
+
+    pub fn score(&self) -> u32 {
+        self.found.iter().map(|f| self.puzzle.score(&f.word)).sum()
+    }
+
+    pub fn max_score(&self) -> u32 {
+        self.puzzle.max_score()
+    }
+
+    pub fn rank(&self) -> Rank {
+        Rank::for_score(self.score(), self.max_score())
+    }
+
+    // how many answers starting with each letter are still to be found
+    pub fn remaining_by_letter(&self) -> BTreeMap<char, usize> {
+        let mut res = BTreeMap::new();
+        for a in self.puzzle.answers.iter() {
+            let n = res.entry(a.chars().next().unwrap_or(' ')).or_insert(0);
+            if !self.is_found(a) {
+                *n += 1;
+            }
+        }
+        res
+    }
+
+    // the next rank up and how many points are still needed for it
+    pub fn next_rank(&self) -> Option<(Rank, u32)> {
+        let next = self.rank().next()?;
+        Some((next, next.threshold(self.max_score()) - self.score()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // center o, max score 1 + 1 + 17 + 6 + 1 = 26
+    fn state() -> GameState {
+        let words = ["clot", "cool", "locomotion", "motion", "toot"].map(String::from).to_vec();
+        GameState::new(Puzzle::new(['o', 'l', 'c', 'm', 't', 'i', 'n'], words))
+    }
+
+    #[test]
+    fn check_order() {
+        let mut s = state();
+        s.submit("cool");
+        // too short wins over everything, then letters, center, found, list
+        assert_eq!(s.check("zzz"), Submission::TooShort);
+        assert_eq!(s.check("zoom"), Submission::BadLetter);
+        assert_eq!(s.check("mint"), Submission::MissingCenter);
+        assert_eq!(s.check("cool"), Submission::AlreadyFound);
+        assert_eq!(s.check("loot"), Submission::NotInList);
+        assert_eq!(s.check("toot"), Submission::Accepted(1));
+        assert_eq!(s.check("motion"), Submission::Accepted(6));
+        assert_eq!(s.check("locomotion"), Submission::Pangram(17));
+    }
+
+    #[test]
+    fn submit_counts() {
+        let mut s = state();
+        assert_eq!(s.max_score(), 26);
+        assert!(s.submit("motion").is_ok());
+        assert!(!s.submit("motion").is_ok());
+        assert!(!s.submit("loot").is_ok());
+        assert!(!s.submit("loot").is_ok());
+        assert!(s.submit("locomotion").is_ok());
+
+        assert_eq!(s.score(), 23);
+        let found: Vec<&str> = s.found.iter().map(|f| f.word.as_str()).collect();
+        assert_eq!(found, ["motion", "locomotion"]);
+        assert_eq!(s.misses.get("not-in-list"), Some(&2));
+        assert_eq!(s.misses.get("already-found"), Some(&1));
+        assert_eq!(s.misses.len(), 2);
+    }
+
+    #[test]
+    fn rank_boundaries() {
+        for r in Rank::ALL {
+            let t = r.threshold(100);
+            assert_eq!(t, r.percent());
+            assert_eq!(Rank::for_score(t, 100), r);
+            if t > 0 {
+                assert!(Rank::for_score(t - 1, 100) < r);
+            }
+        }
+        // thresholds round to the nearest point
+        assert_eq!(Rank::Genius.threshold(26), 18);
+        assert_eq!(Rank::for_score(17, 26), Rank::Amazing);
+        assert_eq!(Rank::for_score(18, 26), Rank::Genius);
+    }
+
+    #[test]
+    fn next_rank() {
+        let mut s = state();
+        assert_eq!(s.next_rank(), Some((Rank::GoodStart, 1)));
+        s.submit("motion");
+        assert_eq!(s.rank(), Rank::Solid);
+        assert_eq!(s.next_rank(), Some((Rank::Nice, 1)));
+        s.submit("locomotion");
+        assert_eq!(s.rank(), Rank::Genius);
+        assert_eq!(s.next_rank(), Some((Rank::QueenBee, 3)));
+        s.submit("clot");
+        s.submit("cool");
+        s.submit("toot");
+        assert_eq!(s.rank(), Rank::QueenBee);
+        assert_eq!(s.next_rank(), None);
+    }
+}
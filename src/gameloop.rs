@@ -1,35 +1,231 @@
 //use std::sync::{Arc,Mutex};
-use std::time::Duration;
-use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 use std::path::Path;
 use std::string::ToString;
 
 use sdl2::EventPump;
-use sdl2::event::Event;
+use sdl2::gfx::primitives::DrawRenderer;
+use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
+use sdl2::mouse::{MouseButton, MouseWheelDirection};
 use sdl2::render::Canvas;
 use sdl2::video::Window;
-use sdl2::video::WindowContext;
 use sdl2::pixels::Color;
 use sdl2::rect::Rect;
-use sdl2::ttf::Font;
-use sdl2::render::TextureCreator;
+
+use crate::layout::{Hex, Layout};
+use crate::text::TextCache;
+
+use bumbling::{BumblingError, GameState, Rank};
+use bumbling::error::Result;
+use bumbling::game::{shuffle_letters, Submission};
+use bumbling::hints::{self, Grid};
 
 const FRAMERATE: u32 = 128;
+// ms to wait for input when nothing is moving
+const IDLE_WAIT: u32 = 1000;
+const TOAST_TIME: Duration = Duration::from_millis(1200);
+const SHAKE_TIME: Duration = Duration::from_millis(350);
+const FLASH_TIME: Duration = Duration::from_millis(300);
+
+struct Toast {
+    outcome: Submission,
+    at: Instant,
+}
 
-fn shuffle_letters(letters: &mut [char; 7]) {
-    let mut r: u32 = rand::random();
-    for i in 1..7 {
-        let rn: usize = (6 - r % (7 - i)) as usize;
-        r /= 7 - i;
-        let tmp = letters[i as usize];
-        letters[i as usize] = letters[rn];
-        letters[rn] = tmp;
+pub fn sdl_err(e: impl ToString) -> BumblingError {
+    BumblingError::Sdl(e.to_string())
+}
+
+// what a click or tap landed on
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Letter(usize),
+    Delete,
+    Shuffle,
+    Reveal,
+    Enter,
+    Sort,
+    Remaining,
+    Hints,
+}
+
+const BUTTONS: [(Target, &str); 4] = [
+    (Target::Delete, "Delete"),
+    (Target::Shuffle, "Shuffle"),
+    (Target::Reveal, "Reveal"),
+    (Target::Enter, "Enter"),
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SortBy {
+    Alpha,
+    Found,
+    Length,
+}
+
+impl SortBy {
+    fn next(self) -> SortBy {
+        match self {
+            SortBy::Alpha => SortBy::Found,
+            SortBy::Found => SortBy::Length,
+            SortBy::Length => SortBy::Alpha,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            SortBy::Alpha => "a-z",
+            SortBy::Found => "found",
+            SortBy::Length => "length",
+        }
+    }
+}
+
+fn found_words(state: &GameState, sort: SortBy) -> Vec<&str> {
+    let mut res: Vec<&str> = state.found.iter().map(|f| f.word.as_str()).collect();
+    match sort {
+        SortBy::Alpha => res.sort(),
+        SortBy::Found => {},
+        // longest first, alphabetical within a length
+        SortBy::Length => res.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b))),
+    }
+    res
+}
+
+// left to right along the top of the found panel
+const HEADER: [Target; 3] = [Target::Sort, Target::Remaining, Target::Hints];
+
+// everything on screen that isn't part of the game state
+struct Ui {
+    letters: [char; 7],
+    word: String,
+    toast: Option<Toast>,
+    hover: Option<Target>,
+    layout: Layout,
+    // drawable size in pixels and pixels per window point
+    size: (u32, u32),
+    scale: f32,
+    resized: bool,
+    // something changed since the last frame was drawn
+    dirty: bool,
+    sort: SortBy,
+    show_remaining: bool,
+    show_hints: bool,
+    // found list scroll position in columns
+    scroll: usize,
+}
+
+impl Ui {
+    fn resize(&mut self, canvas: &Canvas<Window>) -> Result<()> {
+        let (w, h) = canvas.output_size().map_err(sdl_err)?;
+        let (ww, _) = canvas.window().size();
+        self.size = (w, h);
+        self.scale = if ww > 0 { w as f32 / ww as f32 } else { 1.0 };
+        self.layout = Layout::new(w, h, self.scale);
+        self.resized = false;
+        Ok(())
+    }
+
+    // mouse positions are in window points, the layout is in pixels
+    fn target_at(&self, x: i32, y: i32) -> Option<Target> {
+        hit_test(&self.layout, (x as f32 * self.scale) as i32, (y as f32 * self.scale) as i32)
+    }
+
+    fn submit(&mut self, state: &mut GameState) {
+        // a stray enter isn't a guess
+        if self.word.is_empty() {
+            return;
+        }
+        let outcome = state.submit(&self.word);
+        if outcome.is_ok() {
+            self.word.clear();
+        }
+        self.toast = Some(Toast { outcome, at: Instant::now() });
     }
+
+    // the toast, shake and flash all run for TOAST_TIME after a submission
+    fn animating(&self) -> bool {
+        self.toast.as_ref().is_some_and(|t| t.at.elapsed() < TOAST_TIME)
+    }
+
+    fn remaining_lines(&self, state: &GameState) -> usize {
+        state.remaining_by_letter().len().div_ceil(self.layout.columns)
+    }
+
+    // lines of the found panel left for words, between the header and the counts
+    fn list_lines(&self, state: &GameState) -> usize {
+        let mut lines = self.layout.lines - 2;
+        if self.show_remaining {
+            lines = lines.saturating_sub(self.remaining_lines(state));
+        }
+        lines.max(1)
+    }
+
+    fn max_scroll(&self, state: &GameState) -> usize {
+        state.found.len().div_ceil(self.list_lines(state)).saturating_sub(self.layout.columns)
+    }
+
+    fn scroll_by(&mut self, columns: i32, state: &GameState) {
+        let scroll = self.scroll.min(self.max_scroll(state)) as i32 + columns;
+        self.scroll = (scroll.max(0) as usize).min(self.max_scroll(state));
+    }
+
+    // opening the grid counts as using a hint
+    fn toggle_hints(&mut self, state: &mut GameState) {
+        self.show_hints = !self.show_hints;
+        if self.show_hints {
+            state.use_hint("grid");
+        }
+    }
+
+    fn press(&mut self, target: Target, state: &mut GameState) {
+        match target {
+            Target::Letter(i) => self.word.push(self.letters[i]),
+            Target::Delete => {
+                self.word.pop();
+            },
+            Target::Shuffle => shuffle_letters(&mut self.letters),
+            // whatever is typed narrows down which word gets revealed
+            Target::Reveal => {
+                hints::reveal(state, &self.word);
+            },
+            Target::Enter => self.submit(state),
+            Target::Sort => self.sort = self.sort.next(),
+            Target::Remaining => self.show_remaining = !self.show_remaining,
+            Target::Hints => self.toggle_hints(state),
+        }
+    }
+}
+
+fn hit_test(layout: &Layout, x: i32, y: i32) -> Option<Target> {
+    if let Some(i) = layout.cells.iter().position(|c| c.contains(x, y)) {
+        return Some(Target::Letter(i));
+    }
+    if let Some(i) = layout.header().iter().position(|r| r.contains_point((x, y))) {
+        return Some(HEADER[i]);
+    }
+    layout.buttons.iter().position(|b| b.contains_point((x, y))).map(|i| BUTTONS[i].0)
 }
 
-pub fn control(pump: &mut EventPump, letters: &mut [char; 7], word: &mut String, found: &mut BTreeMap<&str, bool>) -> bool {
-    for event in pump.poll_iter() {
+// touches also come through as mouse events from this id, skip those so a tap
+// doesn't count twice
+const TOUCH_MOUSE_ID: u32 = u32::MAX;
+
+// waits up to timeout ms for input, then handles everything that is queued
+fn control(pump: &mut EventPump, ui: &mut Ui, state: &mut GameState, timeout: u32) -> bool {
+    let first = match pump.wait_event_timeout(timeout) {
+        Some(e) => e,
+        None => {
+            return true;
+        },
+    };
+
+    for event in std::iter::once(first).chain(pump.poll_iter()) {
+        // pointer movement only matters when it changes what is highlighted
+        if !matches!(event, Event::MouseMotion {..}) {
+            ui.dirty = true;
+        }
         match event {
             Event::Quit {..} => {
                 return false;
@@ -38,187 +234,383 @@ pub fn control(pump: &mut EventPump, letters: &mut [char; 7], word: &mut String,
                 //println!("te: {:?}", text);
                 for c in text.chars() {
                     if c == '?' || c == '/' {
-                        shuffle_letters(letters);
+                        shuffle_letters(&mut ui.letters);
+                    }
+                    if c == '#' {
+                        ui.show_remaining = !ui.show_remaining;
                     }
-                    for li in 0..7 {
-                        if letters[li] == c.to_ascii_lowercase() {
-                            word.push(c.to_ascii_lowercase());
-                            break;
-                        }
-                        //println!("{}", word);
+                    if c == '!' {
+                        ui.toggle_hints(state);
+                    }
+                    if ui.letters.contains(&c.to_ascii_lowercase()) {
+                        ui.word.push(c.to_ascii_lowercase());
                     }
                 }
             },
             Event::KeyDown {keycode: Some(key), repeat: false, .. } => {
                 match key {
                     Keycode::BACKSPACE => {
-                        word.pop();
+                        ui.word.pop();
                     },
                     Keycode::RETURN => {
-                        //println!("WORD: {}", word);
-                        if let Some(vv) = found.get_mut(&word as &str) {
-                            //println!("found");
-                            *vv = true;
-                            word.clear();
-                        }
+                        ui.submit(state);
                     },
                     Keycode::ESCAPE => {
-                        word.clear();
+                        ui.word.clear();
+                    },
+                    Keycode::TAB => {
+                        ui.sort = ui.sort.next();
+                    },
+                    Keycode::PAGEUP => {
+                        ui.scroll_by(-(ui.layout.columns as i32), state);
+                    },
+                    Keycode::PAGEDOWN => {
+                        ui.scroll_by(ui.layout.columns as i32, state);
                     },
                     _k => {
                         //println!("kd: {:?}", _k);
                     },
                 };
             },
+            Event::MouseButtonDown {which, mouse_btn: MouseButton::Left, x, y, .. } if which != TOUCH_MOUSE_ID => {
+                if let Some(t) = ui.target_at(x, y) {
+                    ui.press(t, state);
+                }
+            },
+            Event::MouseMotion {which, x, y, .. } if which != TOUCH_MOUSE_ID => {
+                let hover = ui.target_at(x, y);
+                if hover != ui.hover {
+                    ui.hover = hover;
+                    ui.dirty = true;
+                }
+            },
+            Event::MouseWheel {y, direction, .. } => {
+                let y = if direction == MouseWheelDirection::Flipped { -y } else { y };
+                ui.scroll_by(-y, state);
+            },
+            Event::FingerDown {x, y, .. } => {
+                // finger positions are 0..1 across the window
+                let x = (x * ui.size.0 as f32) as i32;
+                let y = (y * ui.size.1 as f32) as i32;
+                if let Some(t) = hit_test(&ui.layout, x, y) {
+                    ui.press(t, state);
+                }
+            },
+            Event::Window {win_event: WindowEvent::Leave, .. } => {
+                ui.hover = None;
+            },
+            Event::Window {win_event: WindowEvent::SizeChanged(..) | WindowEvent::DisplayChanged(..), .. } => {
+                ui.resized = true;
+            },
             _x => {
                 //println!("?: {:?}", _x);
             },
         }
     }
-    return true;
+    true
 }
 
-fn render_text_rect(can: &mut Canvas<Window>, tc: &TextureCreator<WindowContext>, font: &mut Font, txt: impl ToString + std::fmt::Display, rect: Rect) {
-    render_text_rect_color(can, tc, font, txt, rect, Color::RGBA(0xff, 0xff, 0xff, 0xff), true);
+fn render_text_rect(can: &mut Canvas<Window>, text: &mut TextCache, txt: impl ToString + std::fmt::Display, rect: Rect) -> Result<()> {
+    render_text_rect_color(can, text, txt, rect, Color::RGBA(0xff, 0xff, 0xff, 0xff), true)
 }
 
-fn render_text_rect_color(can: &mut Canvas<Window>, tc: &TextureCreator<WindowContext>, font: &mut Font, txt: impl ToString + std::fmt::Display, mut rect: Rect, color: Color, center: bool) {
-    let surf = font.render(&txt.to_string()).blended(color).unwrap();
-    let wmul = rect.width() as f32 / surf.width() as f32;
-    let hmul = rect.height() as f32 / surf.height() as f32;
+fn render_text_rect_color(can: &mut Canvas<Window>, text: &mut TextCache, txt: impl ToString + std::fmt::Display, mut rect: Rect, color: Color, center: bool) -> Result<()> {
+    let texture = text.get(&txt.to_string(), color)?;
+    let query = texture.query();
+    let wmul = rect.width() as f32 / query.width as f32;
+    let hmul = rect.height() as f32 / query.height as f32;
 
     if wmul > hmul {
-        let rw = (surf.width() as f32 * hmul) as u32;
+        let rw = (query.width as f32 * hmul) as u32;
         let ro = ((rect.width() - rw) / 2) as i32;
         rect.set_width(rw);
         if center {
             rect.set_x(rect.x() + ro);
         }
     } else {
-        let rh = (surf.height() as f32 * wmul) as u32;
+        let rh = (query.height as f32 * wmul) as u32;
         let ro = ((rect.height() - rh) / 2) as i32;
         rect.set_height(rh);
         rect.set_y(rect.y() + ro);
     }
 
-    let texture = tc.create_texture_from_surface(&surf).unwrap();
-    can.copy(&texture, None, Some(rect)).unwrap();
+    can.copy(texture, None, Some(rect)).map_err(sdl_err)
 }
 
-fn letrec(i: usize) -> Rect {
-    match i {
-        0 => Rect::new(110, 195, 90, 90),
-        1 => Rect::new(60, 95, 90, 90),
-        2 => Rect::new(160, 95, 90, 90),
-        3 => Rect::new(10, 195, 90, 90),
-        4 => Rect::new(210, 195, 90, 90),
-        5 => Rect::new(60, 295, 90, 90),
-        6 => Rect::new(160, 295, 90, 90),
-        _ => unreachable!(),
+fn hover_color(hover: bool, color: Color) -> Color {
+    if hover {
+        Color::RGB(color.r.saturating_add(0x22), color.g.saturating_add(0x22), color.b.saturating_add(0x22))
+    } else {
+        color
     }
 }
 
-fn render(can: &mut Canvas<Window>, tc: &TextureCreator<WindowContext>, font: &mut Font, letters: &mut [char; 7], word: &mut String, found: &mut BTreeMap<&str, bool>) {
-    can.set_draw_color(Color::RGB(0, 0, 0));
-    can.clear();
-
-    //let canvsize: (u32, u32) = can.output_size().expect("Could not get canvas size.");
+// one dot per rank spread evenly along a line, filled up to the current one
+fn render_ladder(can: &mut Canvas<Window>, text: &mut TextCache, state: &GameState, area: Rect) -> Result<()> {
+    let rank = state.rank();
+    let n = Rank::ALL.len() as i32;
+    let step = (area.width() as i32 - 20) / (n - 1);
+    let y = area.y() + 12;
 
     can.set_draw_color(Color::RGB(0x44, 0x44, 0x44));
-    for i in 1..7 {
-        can.fill_rect(letrec(i)).unwrap();
+    can.fill_rect(Rect::new(area.x() + 10, y - 1, (step * (n - 1)) as u32, 3)).map_err(sdl_err)?;
+
+    can.set_draw_color(Color::RGB(0xff, 0xff, 0));
+    can.fill_rect(Rect::new(area.x() + 10, y - 1, (step * rank as i32) as u32, 3)).map_err(sdl_err)?;
+
+    for (i, r) in Rank::ALL.iter().enumerate() {
+        let x = area.x() + 10 + step * i as i32;
+        let size = if *r == rank { 14 } else { 8 };
+        can.set_draw_color(if *r <= rank {
+            Color::RGB(0xff, 0xff, 0)
+        } else {
+            Color::RGB(0x44, 0x44, 0x44)
+        });
+        can.fill_rect(Rect::from_center((x, y), size, size)).map_err(sdl_err)?;
     }
 
-    can.fill_rect(Rect::new(310, 10, 320, 460)).unwrap();
+    let label = match state.next_rank() {
+        Some((next, pts)) => format!("{} - {} to {}", rank.name(), pts, next.name()),
+        None => rank.name().to_string(),
+    };
+    render_text_rect_color(can, text, label,
+                           Rect::new(area.x(), y + 14, area.width(), area.height() - 26),
+                           Color::RGB(0xff, 0xff, 0xff), false)
+}
+
+fn render_toast(can: &mut Canvas<Window>, text: &mut TextCache, toast: &Toast, area: Rect) -> Result<()> {
+    let color = match toast.outcome {
+        Submission::Pangram(_) => Color::RGB(0xff, 0xff, 0),
+        o if o.is_ok() => Color::RGB(0xff, 0xff, 0xff),
+        _ => Color::RGB(0xff, 0x66, 0x66),
+    };
 
-    can.set_draw_color(Color::RGB(0x66, 0x66, 0));
-    can.fill_rect(letrec(0)).unwrap();
+    can.set_draw_color(Color::RGB(0x22, 0x22, 0x22));
+    can.fill_rect(area).map_err(sdl_err)?;
+    render_text_rect_color(can, text, toast.outcome.message(), area, color, true)
+}
 
-    let mut lset_max: u32 = 0;
-    for i in 0..7 {
-        lset_max |= 1 << (letters[i] as u32 & 31);
-        render_text_rect(can, tc, font,
-                         letters[i].to_ascii_uppercase(), letrec(i));
+// the letter grid and two-letter list over the found words, one monospaced
+// line each, padded to the same width so they all scale alike
+fn render_hints(can: &mut Canvas<Window>, text: &mut TextCache, area: Rect, line_h: u32, state: &GameState) -> Result<()> {
+    let mut lines = Grid::new(state).lines();
+    lines.push(String::new());
+    lines.extend(hints::two_letter_lines(state));
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let line_h = line_h.min(area.height() / lines.len() as u32);
+
+    can.set_draw_color(Color::RGB(0x22, 0x22, 0x22));
+    can.fill_rect(area).map_err(sdl_err)?;
+    for (n, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let r = Rect::new(area.x() + 4, area.y() + (n as u32 * line_h) as i32, area.width() - 8, line_h);
+        render_text_rect_color(can, text, format!("{:<width$}", line, width = width), r, Color::RGB(0xff, 0xff, 0xff), false)?;
     }
+    Ok(())
+}
 
-    if word.len() > 0 {
-        render_text_rect(can, tc, font, word, Rect::new(10, 10, 290, 75));
+fn render_found(can: &mut Canvas<Window>, text: &mut TextCache, ui: &Ui, state: &GameState) -> Result<()> {
+    let layout = &ui.layout;
+    let white = Color::RGB(0xff, 0xff, 0xff);
+    let gray = Color::RGB(0xaa, 0xaa, 0xaa);
+
+    for (target, r) in HEADER.iter().zip(layout.header()) {
+        can.set_draw_color(hover_color(ui.hover == Some(*target), Color::RGB(0x33, 0x33, 0x33)));
+        can.fill_rect(r).map_err(sdl_err)?;
+        let (label, on) = match target {
+            Target::Sort => (format!("by {}", ui.sort.name()), true),
+            Target::Remaining => ("left".to_string(), ui.show_remaining),
+            _ => ("hints".to_string(), ui.show_hints),
+        };
+        render_text_rect_color(can, text, label, r, if on { white } else { gray }, false)?;
     }
 
-    let mut h = 0;
-    let mut w = 0;
+    if ui.show_hints {
+        return render_hints(can, text, layout.overlay(), layout.word.1, state);
+    }
 
-    let mut f = 0;
-    let mut mscore = 0;
-    let mut tscore = 0;
-    for (ans, isf) in found.iter() {
-        let mut lset: u32 = 0;
-        for c in ans.chars() {
-            lset |= 1 << (c as u32 & 31);
+    let mut line = 1;
+    if ui.show_remaining {
+        for (n, (l, left)) in state.remaining_by_letter().iter().enumerate() {
+            let color = if *left == 0 { gray } else { white };
+            render_text_rect_color(can, text, format!("{} {}", l.to_ascii_uppercase(), left),
+                                   layout.slot(line + n / layout.columns, n % layout.columns), color, false)?;
         }
+        line += ui.remaining_lines(state);
+    }
 
-        let pgram = lset == lset_max;
-        let score = if ans.len() < 5 { 1 } else {
-            ans.len() + if pgram {7} else {0}
+    let found = found_words(state, ui.sort);
+    let lines = ui.list_lines(state);
+    let first = ui.scroll.min(ui.max_scroll(state)) * lines;
+    let shown = found.iter().skip(first).take(lines * layout.columns);
+    for (n, ans) in shown.enumerate() {
+        let color = if state.puzzle.is_pangram(ans) {
+            Color::RGB(0xff, 0xff, 0)
+        } else {
+            white
         };
-        mscore += score;
-
-        if *isf {
-            tscore += score;
-            let color = if pgram {
-                Color::RGB(0xff, 0xff, 0)
-            } else {
-                Color::RGB(0xff, 0xff, 0xff)
-            };
-            render_text_rect_color(can, tc, font, ans,
-                                   Rect::new(310 + w * 80, 10 + h * 20, 75, 20), color, false);
-            h = (h + 1) % 22;
-            if h == 0 {
-                w += 1;
-            }
-            f += 1;
-        }
+        render_text_rect_color(can, text, ans, layout.slot(line + n % lines, n / lines), color, false)?;
+    }
+
+    let counts = layout.counts();
+    let (cw, ch) = layout.word;
+    render_text_rect(can, text, format!("{}/{}", state.found.len(), state.puzzle.answers.len()),
+                     Rect::new(counts.x(), counts.y(), cw, ch))?;
+    if found.len() > lines * layout.columns {
+        let last = found.len().min(first + lines * layout.columns);
+        render_text_rect_color(can, text, format!("{}-{} of {}", first + 1, last, found.len()),
+                               Rect::new(counts.x() + cw as i32, counts.y(), counts.width().saturating_sub(2 * cw), ch),
+                               gray, true)?;
+    }
+    render_text_rect(can, text, format!("{}/{}", state.score(), state.max_score()),
+                     Rect::new(counts.right() - cw as i32, counts.y(), cw, ch))
+}
+
+// filled, with an antialiased edge in the same color
+fn fill_hex(can: &mut Canvas<Window>, hex: &Hex, color: Color) -> Result<()> {
+    let (xs, ys) = hex.corners();
+    can.filled_polygon(&xs, &ys, color).map_err(sdl_err)?;
+    can.aa_polygon(&xs, &ys, color).map_err(sdl_err)
+}
+
+fn render(can: &mut Canvas<Window>, text: &mut TextCache, ui: &Ui, state: &GameState) -> Result<()> {
+    let layout = &ui.layout;
+    let toast = ui.toast.as_ref();
+    let elapsed = toast.map_or(TOAST_TIME, |t| t.at.elapsed());
+    let outcome = toast.map(|t| t.outcome);
+
+    can.set_draw_color(Color::RGB(0, 0, 0));
+    can.clear();
+
+    for i in 1..7 {
+        fill_hex(can, &layout.cells[i], hover_color(ui.hover == Some(Target::Letter(i)), Color::RGB(0x44, 0x44, 0x44)))?;
+    }
+
+    can.set_draw_color(Color::RGB(0x44, 0x44, 0x44));
+    can.fill_rect(layout.found).map_err(sdl_err)?;
+
+    // the center lights up for a moment when a word is accepted
+    let center = match outcome {
+        Some(o) if o.is_ok() && elapsed < FLASH_TIME => {
+            let t = 1.0 - elapsed.as_secs_f32() / FLASH_TIME.as_secs_f32();
+            let v = (0x66 as f32 + (0xff - 0x66) as f32 * t) as u8;
+            Color::RGB(v, v, 0)
+        },
+        _ => hover_color(ui.hover == Some(Target::Letter(0)), Color::RGB(0x66, 0x66, 0)),
+    };
+    fill_hex(can, &layout.cells[0], center)?;
+    let (xs, ys) = layout.cells[0].corners();
+    can.aa_polygon(&xs, &ys, Color::RGB(0xff, 0xff, 0)).map_err(sdl_err)?;
+
+    for (i, l) in ui.letters.iter().enumerate() {
+        let color = if i == 0 { Color::RGB(0xff, 0xff, 0) } else { Color::RGB(0xff, 0xff, 0xff) };
+        render_text_rect_color(can, text,
+                               l.to_ascii_uppercase(), layout.cells[i].label(), color, true)?;
+    }
+
+    for (i, (target, label)) in BUTTONS.iter().enumerate() {
+        let r = layout.buttons[i];
+        can.set_draw_color(hover_color(ui.hover == Some(*target), Color::RGB(0x33, 0x33, 0x33)));
+        can.fill_rect(r).map_err(sdl_err)?;
+        render_text_rect(can, text, label, Rect::new(r.x() + 8, r.y() + 4, r.width() - 16, r.height() - 8))?;
     }
 
-    render_text_rect(can, tc, font, format!("{}/{}", f, found.len()),
-                     Rect::new(310, 450, 80, 20));
-    render_text_rect(can, tc, font, format!("{}/{}", tscore, mscore),
-                     Rect::new(550, 450, 80, 20));
+    if !ui.word.is_empty() {
+        // rejected words shake side to side, dying down over SHAKE_TIME
+        let (dx, color) = match outcome {
+            Some(o) if !o.is_ok() && elapsed < SHAKE_TIME => {
+                let t = elapsed.as_secs_f32();
+                let amp = 12.0 * (1.0 - t / SHAKE_TIME.as_secs_f32());
+                ((amp * (t * 60.0).sin()) as i32, Color::RGB(0xff, 0x66, 0x66))
+            },
+            _ => (0, Color::RGB(0xff, 0xff, 0xff)),
+        };
+        let mut r = layout.input;
+        r.offset(dx, 0);
+        render_text_rect_color(can, text, &ui.word, r, color, true)?;
+    }
+
+    render_found(can, text, ui, state)?;
+    render_ladder(can, text, state, layout.ladder)?;
+
+    // the word being revealed shows where toasts go when there isn't one
+    match toast {
+        Some(t) if elapsed < TOAST_TIME => render_toast(can, text, t, ui.layout.toast)?,
+        _ => {
+            if let Some(r) = hints::current_reveal(state) {
+                render_text_rect_color(can, text, r.shown(), ui.layout.toast, Color::RGB(0xaa, 0xaa, 0xaa), true)?;
+            }
+        },
+    }
 
     can.present();
+    text.end_frame();
+    Ok(())
 }
 
-pub fn gameloop(found: &mut BTreeMap<&str, bool>, letters: &mut [char; 7]) {
-    let sdl_context = sdl2::init().unwrap();
-    let video_subsystem = sdl_context.video().unwrap();
+pub fn gameloop(state: &mut GameState) -> Result<()> {
+    let sdl_context = sdl2::init().map_err(sdl_err)?;
+    let video_subsystem = sdl_context.video().map_err(sdl_err)?;
     video_subsystem.text_input().start();
 
-    let ttf_context = sdl2::ttf::init().unwrap();
-    let mut font = ttf_context.load_font(Path::new("Inconsolata.ttf"), 128).unwrap();
+    let ttf_context = sdl2::ttf::init().map_err(sdl_err)?;
+    let mut font = ttf_context.load_font(Path::new("Inconsolata.ttf"), 128).map_err(sdl_err)?;
     font.set_style(sdl2::ttf::FontStyle::BOLD);
 
-    let window = video_subsystem.window("BumBling", 640, 480)
+    let mut window = video_subsystem.window("BumBling", 640, 480)
         .position_centered()
+        .resizable()
+        .allow_highdpi()
         .build()
-        .unwrap();
+        .map_err(sdl_err)?;
 
-    let mut canvas = window.into_canvas().accelerated().build().unwrap();
-    let texture_creator = canvas.texture_creator();
-
-    let mut event_pump = sdl_context.event_pump().unwrap();
-
-    let mut word = String::new();
-    shuffle_letters(letters);
+    window.set_minimum_size(320, 240).map_err(sdl_err)?;
 
+    let mut canvas = window.into_canvas().accelerated().build().map_err(sdl_err)?;
+    let texture_creator = canvas.texture_creator();
+    let mut text = TextCache::new(font, &texture_creator);
+
+    let mut event_pump = sdl_context.event_pump().map_err(sdl_err)?;
+
+    let mut ui = Ui {
+        letters: state.puzzle.letters,
+        word: String::new(),
+        toast: None,
+        hover: None,
+        layout: Layout::new(640, 480, 1.0),
+        size: (640, 480),
+        scale: 1.0,
+        resized: true,
+        dirty: true,
+        sort: SortBy::Alpha,
+        show_remaining: false,
+        show_hints: false,
+        scroll: 0,
+    };
+    shuffle_letters(&mut ui.letters);
+
+    // frames are only drawn after input, or at FRAMERATE while something is
+    // animating, plus one more to draw its end state
+    let mut animating = false;
     loop {
-        if !control(&mut event_pump, letters, &mut word, found) {
+        let timeout = if animating { 1000 / FRAMERATE } else { IDLE_WAIT };
+        if !control(&mut event_pump, &mut ui, state, timeout) {
             break;
         }
+        if ui.resized {
+            ui.resize(&canvas)?;
+        }
 
-        render(&mut canvas, &texture_creator, &mut font, letters, &mut word, found);
-
-        std::thread::sleep(Duration::new(0, 1_000_000_000u32 / FRAMERATE));
+        let was_animating = animating;
+        animating = ui.animating();
+        if ui.dirty || animating || was_animating {
+            render(&mut canvas, &mut text, &ui, state)?;
+            ui.dirty = false;
+        }
     }
 
     println!("saving...");
+    Ok(())
 }
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::error::{BumblingError, Result};
+use crate::puzzle::{lset, Puzzle};
+
+pub struct GenOptions {
+    pub min_answers: usize,
+    pub max_answers: usize,
+    // pick the puzzle whose max score is closest to this instead of the first one that fits
+    pub target_score: Option<u32>,
+    // candidates are tried in an order shuffled from this, a new one each run
+    // when unset
+    pub seed: Option<u64>,
+}
+
+impl Default for GenOptions {
+    fn default() -> GenOptions {
+        GenOptions {
+            min_answers: 20,
+            max_answers: 80,
+            target_score: None,
+            seed: None,
+        }
+    }
+}
+
+fn letters_of(lset: u32) -> Vec<char> {
+    (1..27).filter(|b| lset & 1 << b != 0).map(|b| (b as u8 + b'a' - 1) as char).collect()
+}
+
+// reads a word list with one word per line, keeping only the words that could
+// appear in a puzzle
+pub fn load_dictionary(src: &str) -> Vec<String> {
+    let mut words: Vec<String> = src.lines()
+        .map(|w| w.trim().to_string())
+        .filter(|w| w.len() >= 4 && w.chars().all(|c| c.is_ascii_lowercase()))
+        .filter(|w| lset(w).count_ones() <= 7)
+        .collect();
+    words.sort();
+    words.dedup();
+    words
+}
+
+pub fn generate(dict: &[String], opts: &GenOptions) -> Result<Puzzle> {
+    let mut by_lset: HashMap<u32, Vec<&str>> = HashMap::new();
+    for w in dict {
+        by_lset.entry(lset(w)).or_default().push(w);
+    }
+
+    let mut seeds: Vec<u32> = by_lset.keys().filter(|l| l.count_ones() == 7).copied().collect();
+    // sorted first, as the map's order differs from run to run
+    seeds.sort();
+    let mut rng = match opts.seed {
+        Some(s) => StdRng::seed_from_u64(s),
+        None => StdRng::from_entropy(),
+    };
+    seeds.shuffle(&mut rng);
+
+    let mut best: Option<(u32, Puzzle)> = None;
+
+    for seed in seeds {
+        let seed_letters = letters_of(seed);
+
+        for (ci, &center) in seed_letters.iter().enumerate() {
+            let others: Vec<char> = seed_letters.iter().enumerate()
+                .filter(|(i, _)| *i != ci)
+                .map(|(_, c)| *c)
+                .collect();
+
+            // every subset of the outer letters, plus the center
+            let cbit = 1 << (center as u32 & 31);
+            let mut answers: Vec<String> = Vec::new();
+            for mask in 0..64u32 {
+                let mut sub = cbit;
+                for (i, c) in others.iter().enumerate() {
+                    if mask & 1 << i != 0 {
+                        sub |= 1 << (*c as u32 & 31);
+                    }
+                }
+                if let Some(ws) = by_lset.get(&sub) {
+                    answers.extend(ws.iter().map(|w| w.to_string()));
+                }
+            }
+
+            if answers.len() < opts.min_answers || answers.len() > opts.max_answers {
+                continue;
+            }
+
+            let mut letters = [center; 7];
+            letters[1..].copy_from_slice(&others);
+            let puzzle = Puzzle::new(letters, answers);
+
+            let target = match opts.target_score {
+                Some(t) => t,
+                None => {
+                    return Ok(puzzle);
+                },
+            };
+
+            let dist = puzzle.max_score().abs_diff(target);
+            if dist == 0 {
+                return Ok(puzzle);
+            }
+            if best.as_ref().is_none_or(|(d, _)| dist < *d) {
+                best = Some((dist, puzzle));
+            }
+        }
+    }
+
+    match best {
+        Some((_, puzzle)) => Ok(puzzle),
+        None => Err(BumblingError::InvalidPuzzle(format!(
+            "no pangram in the word list gives {} to {} answers", opts.min_answers, opts.max_answers))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // two pangram letter sets, cilmnot and aenprst, and a few words that fit
+    // neither or are too short
+    const WORDS: &str = "locomotion\nclot\ncolt\ncool\nmotion\nlotion\ntoot\ntool\nmoot\nmint\nlimit\nomit\n\
+                         onion\ninto\nlion\nloin\ncoil\ncolon\nicon\ntonic\n\
+                         parents\nrest\nstar\ntape\nnest\npant\nsent\nrant\npear\nspent\npants\nparent\n\
+                         paste\nearn\nnear\ntears\n\
+                         zebra\ncat\nstampede\n";
+
+    fn opts(min: usize, score: Option<u32>, seed: u64) -> GenOptions {
+        GenOptions { min_answers: min, max_answers: 80, target_score: score, seed: Some(seed) }
+    }
+
+    #[test]
+    fn pangram_and_min() {
+        let dict = load_dictionary(WORDS);
+        for seed in 0..20 {
+            let p = generate(&dict, &opts(6, None, seed)).unwrap();
+            assert!(p.answers.iter().any(|w| p.is_pangram(w)), "{:?}", p.letters);
+            assert!(p.answers.len() >= 6);
+            assert!(p.answers.iter().all(|w| w.contains(p.center())));
+        }
+        assert!(generate(&dict, &opts(100, None, 0)).is_err());
+    }
+
+    #[test]
+    fn same_seed_same_puzzle() {
+        let dict = load_dictionary(WORDS);
+        let a = generate(&dict, &opts(4, None, 7)).unwrap();
+        let b = generate(&dict, &opts(4, None, 7)).unwrap();
+        assert_eq!(a.letters, b.letters);
+        assert_eq!(a.answers, b.answers);
+    }
+
+    #[test]
+    fn closest_score() {
+        let dict = load_dictionary(WORDS);
+        // every center of both sets, to compare against
+        let mut scores = Vec::new();
+        for seed in 0..40 {
+            scores.push(generate(&dict, &opts(1, Some(0), seed)).unwrap().max_score());
+            scores.push(generate(&dict, &opts(1, Some(1000), seed)).unwrap().max_score());
+        }
+        let (lo, hi) = (*scores.iter().min().unwrap(), *scores.iter().max().unwrap());
+        assert!(lo < hi);
+
+        for seed in 0..10 {
+            assert_eq!(generate(&dict, &opts(1, Some(0), seed)).unwrap().max_score(), lo);
+            assert_eq!(generate(&dict, &opts(1, Some(1000), seed)).unwrap().max_score(), hi);
+            let p = generate(&dict, &opts(1, Some(hi), seed)).unwrap();
+            assert_eq!(p.max_score(), hi);
+            assert!(p.answers.iter().any(|w| p.is_pangram(w)));
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+
+use rand::seq::SliceRandom;
+
+use crate::game::GameState;
+
+// answers still to be found, counted by first letter and length
+pub struct Grid {
+    // every length and first letter the answers have, even once all of
+    // them are found, so the grid keeps its shape
+    pub lengths: Vec<usize>,
+    pub letters: Vec<char>,
+    // counts[letter][length]
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl Grid {
+    pub fn new(state: &GameState) -> Grid {
+        let answers = &state.puzzle.answers;
+        let mut lengths: Vec<usize> = answers.iter().map(|a| a.len()).collect();
+        lengths.sort_unstable();
+        lengths.dedup();
+        let mut letters: Vec<char> = answers.iter().filter_map(|a| a.chars().next()).collect();
+        letters.sort_unstable();
+        letters.dedup();
+
+        let mut counts = vec![vec![0; lengths.len()]; letters.len()];
+        for a in answers.iter().filter(|a| !state.is_found(a)) {
+            let r = letters.iter().position(|l| a.starts_with(*l)).unwrap();
+            let c = lengths.iter().position(|l| *l == a.len()).unwrap();
+            counts[r][c] += 1;
+        }
+
+        Grid { lengths, letters, counts }
+    }
+
+    pub fn row_total(&self, r: usize) -> usize {
+        self.counts[r].iter().sum()
+    }
+
+    pub fn column_total(&self, c: usize) -> usize {
+        self.counts.iter().map(|row| row[c]).sum()
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    // the grid as a table, `-` where nothing is left
+    pub fn lines(&self) -> Vec<String> {
+        let cell = |n: usize| if n == 0 { format!("{:>4}", "-") } else { format!("{:>4}", n) };
+
+        let mut res = Vec::new();
+        let mut head = String::from("   ");
+        for l in self.lengths.iter() {
+            head.push_str(&format!("{:>4}", l));
+        }
+        head.push_str(&format!("{:>4}", "∑"));
+        res.push(head);
+
+        for (r, l) in self.letters.iter().enumerate() {
+            let mut line = format!("{}: ", l.to_ascii_uppercase());
+            for n in self.counts[r].iter() {
+                line.push_str(&cell(*n));
+            }
+            line.push_str(&cell(self.row_total(r)));
+            res.push(line);
+        }
+
+        let mut foot = String::from("∑: ");
+        for c in 0..self.lengths.len() {
+            foot.push_str(&cell(self.column_total(c)));
+        }
+        foot.push_str(&cell(self.total()));
+        res.push(foot);
+        res
+    }
+}
+
+// answers still to be found by their first two letters
+pub fn two_letter(state: &GameState) -> BTreeMap<String, usize> {
+    let mut res = BTreeMap::new();
+    for a in state.puzzle.answers.iter() {
+        let n = res.entry(a.chars().take(2).collect()).or_insert(0);
+        if !state.is_found(a) {
+            *n += 1;
+        }
+    }
+    res
+}
+
+// one line per first letter: `AB-2 AC-1`
+pub fn two_letter_lines(state: &GameState) -> Vec<String> {
+    let mut res: Vec<String> = Vec::new();
+    let mut last = None;
+    for (start, n) in two_letter(state) {
+        let entry = format!("{}-{}", start.to_ascii_uppercase(), n);
+        match res.last_mut() {
+            Some(line) if last == start.chars().next() => {
+                line.push(' ');
+                line.push_str(&entry);
+            },
+            _ => res.push(entry),
+        }
+        last = start.chars().next();
+    }
+    res
+}
+
+// a word being given away a step at a time: its first letter, then one more
+// letter each step up to all but the last, then its length
+pub struct Reveal {
+    pub word: String,
+    pub level: usize,
+}
+
+impl Reveal {
+    // in letters, answers from a dump or selector source needn't be ascii
+    pub fn max_level(&self) -> usize {
+        self.word.chars().count()
+    }
+
+    // `CO…` while letters are coming, `COO_` once the length is out
+    pub fn shown(&self) -> String {
+        let len = self.max_level();
+        let letters = self.level.min(len - 1);
+        let mut res: String = self.word.chars().take(letters).collect::<String>().to_uppercase();
+        if self.level >= len {
+            res.push_str(&"_".repeat(len - letters));
+        } else {
+            res.push('…');
+        }
+        res
+    }
+}
+
+// reveals are recorded as `reveal <word> <level>` hints
+fn parse_reveal(what: &str) -> Option<Reveal> {
+    let mut it = what.strip_prefix("reveal ")?.split(' ');
+    let word = it.next()?.to_string();
+    let level = it.next()?.parse().ok()?;
+    Some(Reveal { word, level })
+}
+
+// the word the last reveal was about, unless it has been found since
+pub fn current_reveal(state: &GameState) -> Option<Reveal> {
+    let last = state.hints.iter().rev().find_map(|h| parse_reveal(&h.what))?;
+    if state.is_found(&last.word) || !state.puzzle.is_answer(&last.word) {
+        return None;
+    }
+    Some(last)
+}
+
+// takes the current word a step further, or picks a new unfound word starting
+// with prefix when there is none, it is fully revealed or doesn't fit prefix
+pub fn reveal(state: &mut GameState, prefix: &str) -> Option<Reveal> {
+    let current = current_reveal(state);
+    let mut next = match current {
+        Some(r) if r.level < r.max_level() && r.word.starts_with(prefix) => r,
+        _ => {
+            let done = current.map(|r| r.word);
+            let left: Vec<&String> = state.puzzle.answers.iter()
+                .filter(|a| a.starts_with(prefix) && !state.is_found(a) && done.as_ref() != Some(*a))
+                .collect();
+            let word = left.choose(&mut rand::thread_rng())?.to_string();
+            Reveal { word, level: 0 }
+        },
+    };
+
+    next.level += 1;
+    state.use_hint(format!("reveal {} {}", next.word, next.level));
+    Some(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::Puzzle;
+
+    fn state() -> GameState {
+        let words = ["clot", "colt", "cool", "locomotion", "motion", "toot", "tool"].map(String::from).to_vec();
+        let mut s = GameState::new(Puzzle::new(['o', 'l', 'c', 'm', 't', 'i', 'n'], words));
+        s.submit("cool");
+        s
+    }
+
+    #[test]
+    fn grid_counts() {
+        let g = Grid::new(&state());
+        assert_eq!(g.lengths, [4, 6, 10]);
+        assert_eq!(g.letters, ['c', 'l', 'm', 't']);
+        assert_eq!(g.counts, [[2, 0, 0], [0, 0, 1], [0, 1, 0], [2, 0, 0]]);
+        assert_eq!((0..4).map(|r| g.row_total(r)).collect::<Vec<_>>(), [2, 1, 1, 2]);
+        assert_eq!((0..3).map(|c| g.column_total(c)).collect::<Vec<_>>(), [4, 1, 1]);
+        assert_eq!(g.total(), 6);
+
+        assert_eq!(g.lines(), [
+            "      4   6  10   ∑",
+            "C:    2   -   -   2",
+            "L:    -   -   1   1",
+            "M:    -   1   -   1",
+            "T:    2   -   -   2",
+            "∑:    4   1   1   6",
+        ]);
+    }
+
+    #[test]
+    fn two_letter_counts() {
+        let s = state();
+        let counts: Vec<(String, usize)> = two_letter(&s).into_iter().collect();
+        assert_eq!(counts, [
+            ("cl".to_string(), 1), ("co".to_string(), 1), ("lo".to_string(), 1),
+            ("mo".to_string(), 1), ("to".to_string(), 2),
+        ]);
+        assert_eq!(two_letter_lines(&s), ["CL-1 CO-1", "LO-1", "MO-1", "TO-2"]);
+    }
+
+    #[test]
+    fn reveal_levels() {
+        let r = |word: &str, level| Reveal { word: word.to_string(), level }.shown();
+        assert_eq!(r("motion", 1), "M…");
+        assert_eq!(r("motion", 5), "MOTIO…");
+        assert_eq!(r("motion", 6), "MOTIO_");
+        assert_eq!(r("crème", 3), "CRÈ…");
+        assert_eq!(r("crème", 5), "CRÈM_");
+    }
+
+    #[test]
+    fn reveal_records() {
+        let mut s = state();
+        let first = reveal(&mut s, "mo").unwrap();
+        assert_eq!((first.word.as_str(), first.level), ("motion", 1));
+        for _ in 0..5 {
+            reveal(&mut s, "");
+        }
+        assert_eq!(s.hints.last().unwrap().what, "reveal motion 6");
+        let cur = current_reveal(&s).unwrap();
+        assert_eq!((cur.word.as_str(), cur.level, cur.shown()), ("motion", 6, "MOTIO_".to_string()));
+        assert_eq!(s.hints.len(), 6);
+
+        // fully revealed, so the next one is another word
+        let next = reveal(&mut s, "").unwrap();
+        assert_ne!(next.word, "motion");
+        assert_eq!(next.level, 1);
+
+        s.submit(&next.word);
+        assert!(current_reveal(&s).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+use sdl2::rect::{Point, Rect};
+
+const SQRT3: f32 = 1.732_050_8;
+
+// where the six outer cells sit around the center, in letter order:
+// top left, top right, left, right, bottom left, bottom right
+const NEIGHBOURS: [(f32, f32); 6] = [
+    (-0.5, -SQRT3 / 2.0),
+    (0.5, -SQRT3 / 2.0),
+    (-1.0, 0.0),
+    (1.0, 0.0),
+    (-0.5, SQRT3 / 2.0),
+    (0.5, SQRT3 / 2.0),
+];
+
+// a pointy-top hexagon
+#[derive(Clone, Copy)]
+pub struct Hex {
+    pub x: f32,
+    pub y: f32,
+    // center to corner
+    pub r: f32,
+}
+
+impl Hex {
+    pub fn center(&self) -> Point {
+        Point::new(self.x as i32, self.y as i32)
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        let dx = (x as f32 - self.x).abs();
+        let dy = (y as f32 - self.y).abs();
+        dx <= self.r * SQRT3 / 2.0 && dx / SQRT3 + dy <= self.r
+    }
+
+    // the corners clockwise from the top, as the x and y lists gfx takes
+    pub fn corners(&self) -> ([i16; 6], [i16; 6]) {
+        let mut xs = [0; 6];
+        let mut ys = [0; 6];
+        for i in 0..6 {
+            let a = (60.0 * i as f32 - 90.0).to_radians();
+            xs[i] = (self.x + self.r * a.cos()).round() as i16;
+            ys[i] = (self.y + self.r * a.sin()).round() as i16;
+        }
+        (xs, ys)
+    }
+
+    // the biggest square that fits, for the letter
+    pub fn label(&self) -> Rect {
+        let side = (self.r * 1.1) as u32;
+        Rect::from_center(self.center(), side, side)
+    }
+}
+
+// positions of everything on screen, worked out from the drawable size in
+// pixels. scale is pixels per window point, above 1 on high-DPI screens.
+pub struct Layout {
+    pub input: Rect,
+    pub cells: [Hex; 7],
+    pub buttons: [Rect; 4],
+    pub ladder: Rect,
+    pub toast: Rect,
+    pub found: Rect,
+    // the found panel is a grid of word sized slots, a header line on top and
+    // the counts on the bottom line
+    pub word: (u32, u32),
+    pub lines: usize,
+    pub columns: usize,
+}
+
+const MARGIN: i32 = 10;
+const WORD_W: f32 = 80.0;
+const WORD_H: f32 = 20.0;
+
+impl Layout {
+    pub fn new(w: u32, h: u32, scale: f32) -> Layout {
+        let (w, h) = (w as i32, h as i32);
+
+        // the hive and its controls take the left half, found words the right
+        let left = (w / 2 - MARGIN).max(100);
+        let input = Rect::new(MARGIN, MARGIN, left as u32, (h / 6).max(20) as u32);
+
+        let ladder_h = (h / 10).max(40);
+        let button_h = (h / 16).max(20);
+        let ladder = Rect::new(MARGIN, h - MARGIN - ladder_h, left as u32, ladder_h as u32);
+        let button_y = ladder.y() - button_h - 6;
+        let button_w = (left - 3 * MARGIN) / 4;
+        let buttons = [0, 1, 2, 3].map(|i| {
+            Rect::new(MARGIN + i * (button_w + MARGIN), button_y, button_w as u32, button_h as u32)
+        });
+
+        // the hive is 3√3 radii wide and 5 high, plus the gaps between cells
+        let hive_top = input.bottom() + MARGIN;
+        let hive_h = (button_y - MARGIN - hive_top).max(50) as f32;
+        let r = (left as f32 / (3.0 * SQRT3 + 0.3)).min(hive_h / 5.2);
+        let step = r * SQRT3 + r * 0.1;
+        let cx = (MARGIN + left / 2) as f32;
+        let cy = hive_top as f32 + hive_h / 2.0;
+
+        let mut cells = [Hex { x: cx, y: cy, r }; 7];
+        for (cell, (dx, dy)) in cells[1..].iter_mut().zip(NEIGHBOURS.iter()) {
+            cell.x += dx * step;
+            cell.y += dy * step;
+        }
+
+        let toast = Rect::from_center((cx as i32, input.bottom() + 14), (left * 2 / 3) as u32, (28.0 * scale) as u32);
+        let found = Rect::new(left + 2 * MARGIN, MARGIN, (w - left - 3 * MARGIN).max(80) as u32, (h - 2 * MARGIN) as u32);
+
+        // words wrap into as many columns as fit
+        let word = ((WORD_W * scale) as u32, (WORD_H * scale) as u32);
+        let lines = ((found.height() / word.1) as usize).max(3);
+        let columns = ((found.width() / word.0) as usize).max(1);
+
+        Layout { input, cells, buttons, ladder, toast, found, word, lines, columns }
+    }
+
+    pub fn slot(&self, line: usize, col: usize) -> Rect {
+        Rect::new(self.found.x() + (col as u32 * self.word.0) as i32, self.found.y() + (line as u32 * self.word.1) as i32,
+                  self.word.0 - self.word.0 / 16, self.word.1)
+    }
+
+    // the top line split in three: sort order, remaining toggle, hints toggle
+    pub fn header(&self) -> [Rect; 3] {
+        let third = self.found.width() / 3;
+        [0, 1, 2].map(|i| Rect::new(self.found.x() + (i * third) as i32, self.found.y(), third - 2, self.word.1))
+    }
+
+    // the found panel under the header, where the hints overlay goes
+    pub fn overlay(&self) -> Rect {
+        Rect::new(self.found.x(), self.found.y() + self.word.1 as i32, self.found.width(),
+                  self.found.height() - self.word.1)
+    }
+
+    pub fn counts(&self) -> Rect {
+        Rect::new(self.found.x(), self.found.bottom() - self.word.1 as i32, self.found.width(), self.word.1)
+    }
+}
@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use chrono_tz::Tz;
+
+use bumbling::generate::GenOptions;
+use bumbling::source::{self, nytbee, NytBee, PuzzleSource};
+
+use crate::cli::Frontend;
+use crate::{exit_on_error, list_dates, run_game_from_file, run_game_from_web, run_generate, today, Fetch};
+
+macro_rules! argmar {
+    () => {"_"};
+}
+
+fn usage(n: usize) {
+    if n > 0 {
+        eprintln!("error: at token #{}", n);
+    }
+    eprintln!(concat!("usage: ./bumbling ((", argmar!(), "w|", argmar!(), "s) <url> <path>? | ", argmar!(), "f <path> | ",
+                      argmar!(), "src <source> | ", argmar!(), "l | ", argmar!(), "g <wordlist> <path>? | ",
+                      "(", argmar!(), "gmin|", argmar!(), "gmax|", argmar!(), "gscore) <n> | --center <letter>)*"));
+    eprintln!("sources: nytbee | select:<selector>[;<url with {{date}}>] | json:<path>");
+    std::process::exit(1);
+}
+
+pub fn run(args: &[String], tz: Tz) {
+    eprintln!("warning: the {}x argument forms are deprecated, see bumbling --help", argmar!());
+
+    let today = format!("{}", today(tz).format("%Y%m%d"));
+    let mut src: Box<dyn PuzzleSource> = Box::new(NytBee);
+    let mut gen_opts = GenOptions::default();
+    let mut center: Option<char> = None;
+    let mut argi = 1;
+    while argi < args.len() {
+        argi += 1;
+        match args[argi - 1].as_str() {
+            v @ (concat!(argmar!(), "w") | concat!(argmar!(), "s")) => {
+                if argi >= args.len() {
+                    usage(argi);
+                }
+                let url = &args[argi];
+                if url.starts_with(argmar!()) {
+                    usage(argi);
+                }
+                argi += 1;
+
+                let date = nytbee::date_from_url(url);
+
+                let strloc;
+                let path = if argi < args.len() && !args[argi].starts_with(argmar!()) {
+                    argi += 1;
+                    Path::new(&args[argi - 1])
+                } else {
+                    strloc = format!("{}.bumble", date.map_or(today.clone(), |d| d.format("%Y%m%d").to_string()));
+                    Path::new(&strloc)
+                };
+
+                // _s only saves
+                let frontend = if v.as_bytes()[1] == b's' { None } else { Some(Frontend::default()) };
+                exit_on_error(run_game_from_web(&*src, Fetch::Url(url), date, tz, center, path, frontend));
+            },
+            concat!(argmar!(), "f") => {
+                let path = if argi < args.len() && !args[argi].starts_with(argmar!()) {
+                    argi += 1;
+                    Path::new(&args[argi - 1])
+                } else {
+                    usage(argi);
+                    unreachable!();
+                };
+
+                exit_on_error(run_game_from_file(path, center, Frontend::default()));
+            },
+            concat!(argmar!(), "src") => {
+                if argi >= args.len() {
+                    usage(argi);
+                }
+                argi += 1;
+                src = match source::from_spec(&args[argi - 1]) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("error: {}", e);
+                        usage(argi - 1);
+                        unreachable!();
+                    },
+                };
+            },
+            concat!(argmar!(), "g") => {
+                if argi >= args.len() || args[argi].starts_with(argmar!()) {
+                    usage(argi);
+                }
+                let dict_path = Path::new(&args[argi]);
+                argi += 1;
+
+                let path = if argi < args.len() && !args[argi].starts_with(argmar!()) {
+                    argi += 1;
+                    Some(Path::new(&args[argi - 1]))
+                } else {
+                    None
+                };
+
+                exit_on_error(run_generate(dict_path, path, &gen_opts));
+            },
+            v @ (concat!(argmar!(), "gmin") | concat!(argmar!(), "gmax") | concat!(argmar!(), "gscore")) => {
+                let n: u32 = match args.get(argi).map(|a| a.parse()) {
+                    Some(Ok(n)) => n,
+                    _ => {
+                        usage(argi);
+                        unreachable!();
+                    },
+                };
+                argi += 1;
+
+                match &v[2..] {
+                    "min" => gen_opts.min_answers = n as usize,
+                    "max" => gen_opts.max_answers = n as usize,
+                    _ => gen_opts.target_score = Some(n),
+                }
+            },
+            "--center" => {
+                center = match args.get(argi).map(|a| a.to_ascii_lowercase()) {
+                    Some(c) if c.len() == 1 && c.as_bytes()[0].is_ascii_lowercase() => c.chars().next(),
+                    _ => {
+                        usage(argi);
+                        unreachable!();
+                    },
+                };
+                argi += 1;
+            },
+            concat!(argmar!(), "l") => {
+                exit_on_error(list_dates(&*src, tz));
+            },
+            _ => {usage(argi - 1);},
+        }
+    }
+
+}
@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate html5ever;
+extern crate markup5ever_rcdom as rcdom;
+
+pub mod error;
+pub mod config;
+pub mod puzzle;
+pub mod game;
+pub mod save;
+pub mod web;
+pub mod source;
+pub mod generate;
+pub mod hints;
+pub mod lint;
+pub mod stats;
+
+pub use error::BumblingError;
+pub use puzzle::Puzzle;
+pub use game::{GameState, Rank};
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crate::puzzle::lset;
+use crate::save;
+
+// a problem with a save, on a line (1-based) or with the file as a whole
+pub struct Diagnostic {
+    pub line: Option<usize>,
+    pub msg: String,
+}
+
+fn letters_of(set: u32) -> String {
+    ('a'..='z').filter(|c| set & 1 << (*c as u32 & 31) != 0).collect()
+}
+
+// everything wrong with a .bumble, in line order with whole-file problems last
+pub fn check(src: &str) -> Vec<Diagnostic> {
+    let mut res = Vec::new();
+    let mut diag = |line: Option<usize>, msg: String| res.push(Diagnostic { line, msg });
+
+    let mut header_letters = None;
+    // and the line it is on
+    let mut center = None;
+    // (line, word) of every word made of nothing but a-z
+    let mut words: Vec<(usize, &str)> = Vec::new();
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+
+    let lines: Vec<&str> = src.split('\n').collect();
+    for (i, line) in lines.iter().enumerate() {
+        let n = i + 1;
+        // bad header values and find times
+        if let Some(msg) = save::check_line(line) {
+            diag(Some(n), msg);
+        }
+        if let Some(h) = line.strip_prefix('#') {
+            let (key, val) = h.split_once(' ').unwrap_or((h, ""));
+            match key {
+                "letters" => header_letters = Some(lset(val.trim())),
+                "center" => center = val.trim().chars().next().map(|c| (n, c)),
+                _ => {},
+            }
+            continue;
+        }
+
+        if line.is_empty() {
+            // the newline ending the last line
+            if i + 1 < lines.len() {
+                diag(Some(n), "empty line".to_string());
+            }
+            continue;
+        }
+
+        let (w, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let (w, found) = match w.strip_suffix('+') {
+            Some(w) => (w, true),
+            None => (w, false),
+        };
+        if !rest.is_empty() && !found {
+            diag(Some(n), format!("unexpected {:?} after {}", rest, w));
+        }
+
+        if let Some(c) = w.chars().find(|c| !c.is_ascii_lowercase()) {
+            let what = if c.is_ascii_uppercase() { "uppercase letter" } else { "stray character" };
+            diag(Some(n), format!("{} {:?} in {:?}", what, c, w));
+            continue;
+        }
+        if w.len() < 4 {
+            diag(Some(n), format!("{} is shorter than 4 letters", w));
+        }
+        match seen.get(w) {
+            Some(first) => diag(Some(n), format!("{} is a duplicate of line {}", w, first)),
+            None => {
+                seen.insert(w, n);
+            },
+        }
+        words.push((n, w));
+    }
+
+    if words.is_empty() {
+        diag(None, "no words".to_string());
+        return res;
+    }
+
+    // the letters are the header's when there is one, otherwise whatever the
+    // words use
+    let all = words.iter().fold(0, |acc, (_, w)| acc | lset(w));
+    let letters = match header_letters {
+        Some(l) => {
+            for (n, w) in words.iter() {
+                if lset(w) & !l != 0 {
+                    diag(Some(*n), format!("{} uses {} which is not one of the letters", w, letters_of(lset(w) & !l)));
+                }
+            }
+            l
+        },
+        None => all,
+    };
+    if letters.count_ones() != 7 {
+        diag(None, format!("{} distinct letters ({}), expected 7", letters.count_ones(), letters_of(letters)));
+    }
+
+    match center {
+        Some((n, c)) if !letters_of(letters).contains(c) => {
+            diag(Some(n), format!("center letter {} is not one of the letters", c));
+        },
+        Some((_, c)) => {
+            for (n, w) in words.iter() {
+                if !w.contains(c) {
+                    diag(Some(*n), format!("{} is missing the center letter {}", w, c));
+                }
+            }
+        },
+        None => {
+            let common = words.iter().fold(all, |acc, (_, w)| acc & lset(w));
+            match common.count_ones() {
+                0 => diag(None, "no letter is in every word, so there is no center".to_string()),
+                1 => {},
+                _ => diag(None, format!("no #center and it could be any of {}", letters_of(common))),
+            }
+        },
+    }
+
+    if letters.count_ones() == 7 && !words.iter().any(|(_, w)| lset(w) == letters) {
+        diag(None, "no pangram".to_string());
+    }
+
+    res.sort_by_key(|d| d.line.unwrap_or(usize::MAX));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diags(src: &str) -> Vec<(Option<usize>, String)> {
+        check(src).into_iter().map(|d| (d.line, d.msg)).collect()
+    }
+
+    #[test]
+    fn line_rules() {
+        let src = "#bumble 2\n\
+                   #letters olcmtin\n\
+                   #center o\n\
+                   Clot\n\
+                   \n\
+                   cot\n\
+                   cool\n\
+                   cool\n\
+                   mint\n\
+                   locomotion\n";
+        assert_eq!(diags(src), [
+            (Some(4), "uppercase letter 'C' in \"Clot\"".to_string()),
+            (Some(5), "empty line".to_string()),
+            (Some(6), "cot is shorter than 4 letters".to_string()),
+            (Some(8), "cool is a duplicate of line 7".to_string()),
+            (Some(9), "mint is missing the center letter o".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn nine_letters() {
+        assert_eq!(diags("bloom\nboot\nlocomotion\nroot\n"), [
+            (None, "9 distinct letters (bcilmnort), expected 7".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn no_pangram() {
+        assert_eq!(diags("#center o\nclot\ncool\nlimo\nmotion\n"), [
+            (None, "no pangram".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn clean() {
+        assert!(diags("#center o\nclot\ncool\nlocomotion\nmotion\n").is_empty());
+    }
+
+    #[test]
+    fn header_values() {
+        let src = "#bumble 3\n\
+                   #timezone Mars/Olympus\n\
+                   #date yesterday\n\
+                   #center x\n\
+                   clot+ 2024-01-01T08:03:12Z\n\
+                   cool+ 8am\n\
+                   locomotion\n";
+        let d = diags(src);
+        let lines: Vec<Option<usize>> = d.iter().map(|(n, _)| *n).collect();
+        assert_eq!(lines, [Some(1), Some(2), Some(3), Some(4), Some(6)]);
+        assert_eq!(d[0].1, "unsupported save version 3");
+        assert_eq!(d[1].1, "unknown timezone Mars/Olympus");
+        assert_eq!(d[3].1, "center letter x is not one of the letters");
+    }
+}
@@ -1,347 +1,528 @@
-use std::io::Write;
-use std::sync::{Arc,Mutex};
-use std::path::Path;
 use std::collections::BTreeMap;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
 
-use curl::easy::Easy;
-use chrono::Utc;
-use chrono_tz::US::Pacific;
-use regex::Regex;
+use std::path::PathBuf;
 
-#[macro_use]
-extern crate html5ever;
-extern crate markup5ever_rcdom as rcdom;
+use chrono::{NaiveDate, Utc};
+use chrono_tz::Tz;
 
-use html5ever::parse_document;
-use html5ever::tendril::{TendrilSink, Tendril};
-use rcdom::{Handle, NodeData, RcDom};
+use bumbling::{GameState, Puzzle};
+use bumbling::{BumblingError, generate};
+use bumbling::config;
+use bumbling::error::Result;
+use bumbling::generate::GenOptions;
+use bumbling::hints::{self, Grid};
+use bumbling::lint;
+use bumbling::stats::{self, Summary};
+use bumbling::puzzle::letters_for;
+use bumbling::save::{read_save, write_save};
+use bumbling::source::{self, nytbee, NytBee, PuzzleSource, SourcePuzzle};
 
 //use dev_tools::*;
 
+mod cli;
+mod legacy;
+mod repl;
+mod tui;
+#[cfg(feature = "sdl")]
 mod gameloop;
+#[cfg(feature = "sdl")]
+mod layout;
+#[cfg(feature = "sdl")]
+mod text;
 
-macro_rules! argmar {
-    () => {"_"};
-}
-
-fn walk(handle: &Handle, words: Arc<Mutex<Vec<String>>>, mut in_list: bool, mut pangram: bool) {
-    let node = handle;
-
-    match node.data {
-        NodeData::Text { ref contents } => {
-            if in_list {
-                let content = contents.borrow();
-                if content.trim() != "" {
-                    let mut word_vec = words.lock().unwrap();
-                    if pangram {
-                        word_vec.insert(0, content.trim().to_string());
-                    } else {
-                        word_vec.push(content.trim().to_string());
-                    }
-                    // println!("{}: {}", pangram, content.trim());
-                }
-            }
-        },
+use cli::{Cli, Command, Frontend, GroupBy};
 
-        NodeData::Element {
-            ref name,
-            ref attrs,
-            ..
-        } => {
-            assert!(name.ns == ns!(html));
-            if in_list && *name.local == *"strong" {
-                pangram = true;
-            }
-            for attr in attrs.borrow().iter() {
-                assert!(attr.name.ns == ns!());
-                if *attr.name.local == *"id" && attr.value == Tendril::from("main-answer-list") {
-                    in_list = true;
-                }
+pub enum Fetch<'a> {
+    Latest,
+    Url(&'a str),
+    Date(NaiveDate),
+}
+
+fn prompt_center(words: &[String], candidates: &[char]) -> Result<[char; 7]> {
+    loop {
+        print!("could not determine center letter, please enter [{}]:", candidates.iter().collect::<String>());
+        std::io::stdout().flush().map_err(|e| BumblingError::Terminal(e.to_string()))?;
+
+        let mut buf = String::new();
+        match std::io::stdin().read_line(&mut buf) {
+            Ok(0) | Err(_) => {
+                println!();
+                return Err(BumblingError::AmbiguousCenter(candidates.to_vec()));
+            },
+            Ok(_) => {},
+        }
+        if let Some(c) = buf.chars().next() {
+            if candidates.contains(&c) {
+                return letters_for(words, Some(c));
             }
-        },
+        }
+    }
+}
 
-        NodeData::ProcessingInstruction { .. } => unreachable!(),
-        _ => {},
+// an explicit center always wins, a guess from the source page is dropped if
+// it doesn't fit the answers. only asks when someone is there to answer.
+fn resolve_letters(words: &[String], center: Option<char>, source_center: Option<char>, interactive: bool) -> Result<[char; 7]> {
+    if center.is_none() {
+        if let Some(Ok(letters)) = source_center.map(|c| letters_for(words, Some(c))) {
+            return Ok(letters);
+        }
     }
 
-    for child in node.children.borrow().iter() {
-        walk(child, words.clone(), in_list, pangram);
+    match letters_for(words, center) {
+        Err(BumblingError::AmbiguousCenter(candidates)) if interactive => prompt_center(words, &candidates),
+        res => res,
     }
 }
 
-fn fetch_words_from_web(url: &str, words: Arc<Mutex<Vec<String>>>) -> Result<(), curl::Error> {
-    let mut curler = Easy::new();
-    curler.url(url)?;
-    curler.write_function(move |data| {
+fn interactive() -> bool {
+    std::io::stdin().is_terminal()
+}
+
+#[cfg(feature = "sdl")]
+fn play_sdl(state: &mut GameState) -> Result<()> {
+    gameloop::gameloop(state)
+}
 
-        // stdout().write_all(data).unwrap();
-        // return Ok(data.len());
+#[cfg(not(feature = "sdl"))]
+fn play_sdl(_state: &mut GameState) -> Result<()> {
+    Err(bumbling::BumblingError::Sdl("built without sdl support, try --tui".to_string()))
+}
 
-        let tendril = Tendril::try_from_byte_slice(data).unwrap();
+fn play(state: &mut GameState, frontend: Frontend, path: &Path) -> Result<()> {
+    match frontend {
+        Frontend::Sdl => play_sdl(state),
+        Frontend::Tui => tui::run(state, path),
+        Frontend::Repl => repl::run(state, path),
+    }
+}
 
-        let dom = parse_document(RcDom::default(), Default::default())
-            .from_utf8()
-            .one(tendril);
+fn load_state(path: &Path, center: Option<char>, interactive: bool) -> Result<GameState> {
+    let save = read_save(path)?;
 
-        walk(&dom.document, words.clone(), false, false);
+    let letters = match (center, save.letters) {
+        (None, Some(l)) => l,
+        _ => resolve_letters(&save.words, center.or(save.center), None, interactive)?,
+    };
+    Ok(save.into_state(letters))
+}
 
-        Ok(data.len())
-    })?;
-    curler.perform()?;
+fn run_game_from_file(path: &Path, center: Option<char>, frontend: Frontend) -> Result<()> {
+    let mut state = load_state(path, center, interactive())?;
 
-    Ok(())
+    let res = play(&mut state, frontend, path);
+
+    write_save(&state, path)?;
+    res
 }
 
-fn get_letters<'a: 'b, 'b>(words: &'a mut Vec<String>, letters: &mut [char; 7], found: &'b mut BTreeMap<&'a str, bool>) -> bool {
-    let mut l_part = 0;
+// the puzzle and the url it came from, when there is one
+fn fetch_puzzle(src: &dyn PuzzleSource, fetch: Fetch) -> Result<(SourcePuzzle, Option<String>)> {
+    Ok(match fetch {
+        Fetch::Url(url) => (src.fetch_url(url)?, Some(url.to_string())),
+        Fetch::Date(d) => (src.fetch_date(d)?, src.url_for_date(d)),
+        Fetch::Latest => (src.fetch_latest()?, None),
+    })
+}
 
-    let mut lset_max: u32 = 0;
+fn run_game_from_web(src: &dyn PuzzleSource, fetch: Fetch, date: Option<NaiveDate>, tz: Tz, center: Option<char>,
+                     path: &Path, frontend: Option<Frontend>) -> Result<()> {
+    let (fetched, url) = fetch_puzzle(src, fetch)?;
+    run_game_from_fetched(fetched, url, date, tz, center, path, frontend)
+}
 
-    'letter_collect: for w in words.iter() {
-        if w == "" {
-            continue;
-        }
-        for c in w.chars() {
-            if c < 'a' || c > 'z' {
-                continue
-            }
-            if lset_max & 1 << (c as u32 & 31) == 0 {
-                lset_max |= 1 << (c as u32 & 31);
-                letters[l_part] = c;
-                l_part += 1;
-
-                if l_part == 7 {
-                    break 'letter_collect;
-                }
-            }
-        }
-    }
+// date is only set when it is known to be the puzzle's, otherwise the source's
+// own idea of the date is used, if it has one
+fn run_game_from_fetched(fetched: SourcePuzzle, url: Option<String>, date: Option<NaiveDate>, tz: Tz,
+                         center: Option<char>, path: &Path, frontend: Option<Frontend>) -> Result<()> {
+    let letters = resolve_letters(&fetched.words, center, fetched.center, frontend.is_some() && interactive())?;
+    let mut puzzle = Puzzle::new(letters, fetched.words);
+    puzzle.date = date.or(fetched.date);
+    puzzle.timezone = Some(tz);
+    puzzle.source = url;
+    let mut state = GameState::new(puzzle);
+
+    // no frontend means just save it
+    let res = match frontend {
+        Some(f) => play(&mut state, f, path),
+        None => Ok(()),
+    };
 
-    if l_part < 7 {
-        eprintln!("error: word set has less than 7 letters");
-        return false;
-    }
+    write_save(&state, path)?;
+    res
+}
 
-    //println!("{:?}", letters);
+fn run_generate(dict_path: &Path, path: Option<&Path>, opts: &GenOptions) -> Result<()> {
+    let src = std::fs::read_to_string(dict_path)
+        .map_err(|e| BumblingError::SaveIo(dict_path.to_path_buf(), e))?;
+    let dict = generate::load_dictionary(&src);
 
-    let mut _pgram_count = 0;
+    let puzzle = generate::generate(&dict, opts)?;
+    let strloc = format!("gen_{}.bumble", puzzle.letters.iter().collect::<String>());
+    let path = path.unwrap_or(Path::new(&strloc));
 
-    for wi in 0..words.len() {
-        let w = &words[wi];
+    let state = GameState::new(puzzle);
+    write_save(&state, path)?;
+    println!("{}: {} answers, max score {}", path.display(), state.puzzle.answers.len(), state.max_score());
+    Ok(())
+}
 
-        if w == "" {
-            continue;
-        }
+fn list_dates(src: &dyn PuzzleSource, tz: Tz) -> Result<()> {
+    for d in src.list_dates(today(tz))? {
+        println!("{}", d.format("%Y%m%d"));
+    }
+    Ok(())
+}
 
-        let mut isfound = false;
+fn exit_on_error(res: Result<()>) {
+    if let Err(e) = res {
+        eprintln!("error: {}", e);
+        std::process::exit(e.exit_code());
+    }
+}
 
-        let mut lset: u32 = 0;
-        let mut ci = 0;
-        for c in w.chars() {
-            if c == '+' {
-                isfound = true;
-                continue;
-            }
-            lset |= 1 << (c as u32 & 31);
-            ci += 1;
-        }
+fn today(tz: Tz) -> NaiveDate {
+    Utc::now().with_timezone(&tz).date_naive()
+}
 
-        if lset & !lset_max != 0 {
-            eprintln!("error: word set has more than 7 letters");
-        }
+fn cli_date(cli: &Cli) -> Result<Option<NaiveDate>> {
+    match cli.date {
+        Some(spec) => Ok(Some(spec.resolve(today(cli.tz))?)),
+        None => Ok(None),
+    }
+}
 
-        let mut i = 0;
-        while i < l_part {
-            if lset & 1 << (letters[i] as u32 & 31) != 0 {
-                i += 1;
-            } else {
-                //println!("word {} missing {}", w, letters[i]);
-                l_part -= 1;
-                if i != l_part {
-                    letters.swap(i, l_part);
-                }
-            }
-        }
+fn save_path(cli: &Cli, date: NaiveDate) -> PathBuf {
+    match cli.output {
+        Some(ref p) => p.clone(),
+        None => PathBuf::from(format!("{}.bumble", date.format("%Y%m%d"))),
+    }
+}
 
-        if lset == lset_max {
-            _pgram_count += 1;
-        }
+fn puzzle_source(cli: &Cli) -> Result<Box<dyn PuzzleSource>> {
+    match cli.source {
+        Some(ref spec) => source::from_spec(spec),
+        None => Ok(Box::new(NytBee)),
+    }
+}
+
+// the day to play or fetch without a --date: today in our zone when the source
+// can fetch by date. the publisher's latest may still be yesterday's, or
+// already tomorrow's, so otherwise it is whatever the latest puzzle says.
+fn default_date(cli: &Cli, src: &dyn PuzzleSource) -> Option<NaiveDate> {
+    let today = today(cli.tz);
+    src.url_for_date(today).map(|_| today)
+}
 
-        found.insert(&words[wi][..ci], isfound);
+fn cmd_play(cli: &Cli) -> Result<()> {
+    let src = puzzle_source(cli)?;
+    let date = match cli_date(cli)? {
+        Some(d) => Some(d),
+        None => default_date(cli, &*src),
+    };
+
+    if let Some(path) = date.map(|d| save_path(cli, d)).filter(|p| p.exists()) {
+        return run_game_from_file(&path, cli.center, cli.frontend);
     }
+    let fetch = match date {
+        Some(d) => Fetch::Date(d),
+        None => Fetch::Latest,
+    };
+    let (fetched, url) = fetch_puzzle(&*src, fetch)?;
 
-    // println!("pangrams: {}", _pgram_count);
-    // println!("letters: {:?}", letters);
+    // the latest puzzle may turn out to be one already played
+    let path = save_path(cli, date.or(fetched.date).unwrap_or_else(|| today(cli.tz)));
+    if path.exists() {
+        return run_game_from_file(&path, cli.center, cli.frontend);
+    }
+    run_game_from_fetched(fetched, url, date, cli.tz, cli.center, &path, Some(cli.frontend))
+}
+
+fn cmd_fetch(cli: &Cli) -> Result<()> {
+    let src = puzzle_source(cli)?;
+    let cli_date = cli_date(cli)?;
 
-    match l_part {
-        0 => {
-            eprintln!("error: no possible valid center letter");
-            return false;
+    let (fetch, date) = match cli.args.first() {
+        Some(url) => {
+            let date = nytbee::date_from_url(url).or(cli_date);
+            (Fetch::Url(url), date)
         },
-        1 => {},
-        x => {
-            'get_center_letter: loop {
-                print!("could not determine center letter, please enter [");
-                for i in 0..x {
-                    print!("{}", letters[i]);
-                }
-                print!("]:");
-                std::io::stdout().flush().unwrap();
-
-                let mut buf = String::new();
-                std::io::stdin().read_line(&mut buf).unwrap();
-                //println!("{}", buf);
-                let c = match buf.chars().next() {
-                    Some(c) => c,
-                    None => {continue;}
-                };
-                for i in 0..x {
-                    if c == letters[i] {
-                        if i != 0 {
-                            letters.swap(0, i);
-                        }
-                        break 'get_center_letter;
-                    }
-                }
-            }
+        None => match cli_date.or_else(|| default_date(cli, &*src)) {
+            Some(d) => (Fetch::Date(d), Some(d)),
+            None => (Fetch::Latest, None),
         },
     };
-    return true;
+
+    // don't throw away progress on a puzzle we already have, looked for before
+    // fetching when the date is known, and after when only the puzzle knows it
+    if let Some(path) = date.map(|d| save_path(cli, d)).filter(|p| p.exists()) {
+        println!("{} already exists", path.display());
+        return Ok(());
+    }
+    let (fetched, url) = fetch_puzzle(&*src, fetch)?;
+    let path = save_path(cli, date.or(fetched.date).unwrap_or_else(|| today(cli.tz)));
+    if path.exists() {
+        println!("{} already exists", path.display());
+        return Ok(());
+    }
+    run_game_from_fetched(fetched, url, date, cli.tz, cli.center, &path, None)?;
+    println!("{}", path.display());
+    Ok(())
 }
 
-fn write_save(found: &BTreeMap<&str, bool>, path: &Path) {
-    let mut res = String::new();
+fn saved_or_dated(cli: &Cli) -> Result<PathBuf> {
+    match cli.args.first() {
+        Some(p) => Ok(PathBuf::from(p)),
+        None => Ok(save_path(cli, cli_date(cli)?.unwrap_or_else(|| today(cli.tz)))),
+    }
+}
 
-    for (w, fnd) in found {
-        if *w == "" {
-            continue;
-        }
-        res.push_str(w);
-        if *fnd {
-            //println!("found {}", w);
-            res.push('+');
+// one line about a single save
+fn print_save_stats(path: &Path, center: Option<char>) -> Result<()> {
+    let state = load_state(path, center, false)?;
+    let next = match state.next_rank() {
+        Some((r, pts)) => format!(", {} to {}", pts, r.name()),
+        None => String::new(),
+    };
+    let misses = if state.misses.is_empty() {
+        String::new()
+    } else {
+        let m: Vec<String> = state.misses.iter().map(|(k, n)| format!("{} {}", k, n)).collect();
+        format!(" (rejected {})", m.join(", "))
+    };
+    let hints = match state.hints.len() {
+        0 => String::new(),
+        1 => ", 1 hint".to_string(),
+        n => format!(", {} hints", n),
+    };
+    println!("{}: {}/{} words, {}/{} points, {}{}{}{}", path.display(),
+             state.found.len(), state.puzzle.answers.len(),
+             state.score(), state.max_score(), state.rank().name(), next, hints, misses);
+    Ok(())
+}
+
+// every .bumble in dir, skipping the ones that won't load
+fn load_dir(dir: &Path) -> Result<Vec<(PathBuf, GameState)>> {
+    let entries = std::fs::read_dir(dir).map_err(|e| BumblingError::SaveIo(dir.to_path_buf(), e))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|x| x == "bumble"))
+        .collect();
+    paths.sort();
+
+    let mut res = Vec::new();
+    for path in paths {
+        match load_state(&path, None, false) {
+            Ok(state) => res.push((path, state)),
+            Err(e) => eprintln!("skipping {}: {}", path.display(), e),
         }
-        res.push('\n');
+    }
+    Ok(res)
+}
+
+fn print_summary(summary: &Summary) {
+    println!("{:<12}{:>9}{:>11}{:>6}  {:<12}{:>6}", "date", "words", "points", "%", "rank", "hints");
+    for d in summary.days.iter() {
+        let date = d.date.map_or("-".to_string(), |d| d.to_string());
+        println!("{:<12}{:>9}{:>11}{:>5}%  {:<12}{:>6}", date, format!("{}/{}", d.found, d.answers),
+                 format!("{}/{}", d.score, d.max_score), d.percent(), d.rank.name(), d.hints);
     }
 
-    if let Err(e) = std::fs::write(path, res) {
-        eprintln!("could not write file: {}", e);
+    println!();
+    let plural = if summary.days.len() == 1 { "" } else { "s" };
+    println!("{} puzzle{}, {:.1} words found on average", summary.days.len(), plural, summary.average_found);
+    println!("genius streak {} (longest {}), queen bee streak {} (longest {})",
+             summary.genius.current, summary.genius.longest, summary.queen_bee.current, summary.queen_bee.longest);
+    let rate = (summary.pangrams_found * 100).checked_div(summary.pangrams).unwrap_or(0);
+    println!("pangrams found {}/{} ({}%)", summary.pangrams_found, summary.pangrams, rate);
+    if !summary.missed.is_empty() {
+        let hardest: Vec<String> = summary.missed.iter().take(10).map(|(w, n)| format!("{} ({})", w, n)).collect();
+        println!("hardest missed: {}", hardest.join(", "));
     }
 }
 
-fn run_game_from_file(path: &Path) {
-    let mut letters: [char; 7] = ['\0'; 7];
-    let words: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
-    let mut found: BTreeMap<&str, bool> = BTreeMap::new();
+fn print_csv(summary: &Summary) {
+    println!("date,found,answers,score,max_score,percent,rank,pangrams_found,pangrams,hints");
+    for d in summary.days.iter() {
+        println!("{},{},{},{},{},{},{},{},{},{}", d.date.map_or(String::new(), |d| d.to_string()),
+                 d.found, d.answers, d.score, d.max_score, d.percent(), d.rank.name(),
+                 d.pangrams_found, d.pangrams, d.hints);
+    }
+}
 
-    if let Ok(src) = std::fs::read_to_string(path) {
-        let mut words = words.lock().unwrap();
-        for w in src.split("\n") {
-            words.push(w.to_string());
-        }
+// a single save in a line, or a table and summary for a directory of them
+// (the current one by default) or several saves
+fn cmd_stats(cli: &Cli) -> Result<()> {
+    let single = match cli.args.as_slice() {
+        [] => cli.date.is_some(),
+        [p] => !Path::new(p).is_dir(),
+        _ => false,
+    };
+    if single && !cli.csv {
+        return print_save_stats(&saved_or_dated(cli)?, cli.center);
+    }
 
-        get_letters(&mut words, &mut letters, &mut found);
+    let mut saves = Vec::new();
+    if cli.args.is_empty() && cli.date.is_none() {
+        saves = load_dir(Path::new("."))?;
+    } else if cli.args.is_empty() {
+        let path = saved_or_dated(cli)?;
+        saves.push((path.clone(), load_state(&path, None, false)?));
+    }
+    for arg in cli.args.iter() {
+        let path = PathBuf::from(arg);
+        if path.is_dir() {
+            saves.extend(load_dir(&path)?);
+        } else {
+            saves.push((path.clone(), load_state(&path, None, false)?));
+        }
+    }
 
-        gameloop::gameloop(&mut found, &mut letters);
+    // saves from before the #date header are named after their date
+    let mut states: Vec<GameState> = saves.into_iter().map(|(path, mut state)| {
+        if state.puzzle.date.is_none() {
+            state.puzzle.date = path.file_stem()
+                .and_then(|s| NaiveDate::parse_from_str(&s.to_string_lossy(), "%Y%m%d").ok());
+        }
+        state
+    }).collect();
+
+    // --center picks out the puzzles with that center, which breaks up the
+    // streaks, so the output says how many are left
+    if let Some(c) = cli.center {
+        let total = states.len();
+        states.retain(|s| s.puzzle.center() == c);
+        let note = format!("only puzzles with center letter {}: {} of {}", c, states.len(), total);
+        if cli.csv {
+            eprintln!("{}", note);
+        } else {
+            println!("{}\n", note);
+        }
+    }
 
-        write_save(&found, path);
+    let summary = stats::summarize(&states, today(cli.tz));
+    if cli.csv {
+        print_csv(&summary);
     } else {
-        eprintln!("could not read file {}", path.display());
+        print_summary(&summary);
     }
+    Ok(())
 }
 
-fn run_game_from_web(url: &str, path: &Path, save_only: bool) {
-    let mut letters: [char; 7] = ['\0'; 7];
-    let words: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
-    let mut found: BTreeMap<&str, bool> = BTreeMap::new();
+fn print_groups<K: Ord + std::fmt::Display>(puzzle: &Puzzle, words: &[&String], key: impl Fn(&str) -> K, label: &str) {
+    let mut groups: BTreeMap<K, Vec<&String>> = BTreeMap::new();
+    for w in words.iter() {
+        groups.entry(key(w)).or_default().push(w);
+    }
 
-    if let Err(e) = fetch_words_from_web(&url, words.clone()) {
-        eprintln!("error: {}", e);
-        return;
+    for (k, ws) in groups {
+        println!("{} {} ({}):", k, label, ws.len());
+        for w in ws {
+            let mark = if puzzle.is_perfect_pangram(w) {
+                " **"
+            } else if puzzle.is_pangram(w) {
+                " *"
+            } else {
+                ""
+            };
+            println!("  {}{}", w, mark);
+        }
     }
+}
 
-    let mut words = words.lock().unwrap();
+fn cmd_solve(cli: &Cli) -> Result<()> {
+    let state = load_state(&saved_or_dated(cli)?, cli.center, false)?;
+    let p = &state.puzzle;
 
-    get_letters(&mut words, &mut letters, &mut found);
+    let words: Vec<&String> = p.answers.iter().filter(|w| !cli.unfound || !state.is_found(w)).collect();
+    match cli.group {
+        GroupBy::Length => print_groups(p, &words, |w| w.len(), "letters"),
+        GroupBy::Letter => print_groups(p, &words, |w| w.chars().next().unwrap_or(' ').to_ascii_uppercase(), "words"),
+    }
 
-    if !save_only {
-        gameloop::gameloop(&mut found, &mut letters);
+    let pangrams = p.answers.iter().filter(|w| p.is_pangram(w)).count();
+    let perfect = p.answers.iter().filter(|w| p.is_perfect_pangram(w)).count();
+    println!();
+    println!("{} answers, max score {}, pangrams {} ({} perfect)", p.answers.len(), p.max_score(), pangrams, perfect);
+    if cli.unfound {
+        println!("{} left to find, worth {} points", words.len(), state.max_score() - state.score());
     }
+    Ok(())
+}
+
+// looking counts as a hint, so it is recorded in the save
+fn cmd_hints(cli: &Cli) -> Result<()> {
+    let path = saved_or_dated(cli)?;
+    let mut state = load_state(&path, cli.center, false)?;
 
-    write_save(&found, path);
+    for line in Grid::new(&state).lines() {
+        println!("{}", line);
+    }
+    println!();
+    for line in hints::two_letter_lines(&state) {
+        println!("{}", line);
+    }
 
+    state.use_hint("grid");
+    write_save(&state, &path)
 }
 
-fn usage(n: usize) {
-    if n > 0 {
-        eprintln!("error: at token #{}", n);
+// prints every problem as path:line: message, failing if there were any
+fn cmd_check(cli: &Cli) -> Result<()> {
+    let mut problems = 0;
+    for arg in cli.args.iter() {
+        let path = Path::new(arg);
+        let src = std::fs::read_to_string(path).map_err(|e| BumblingError::SaveIo(path.to_path_buf(), e))?;
+        for d in lint::check(&src) {
+            match d.line {
+                Some(n) => println!("{}:{}: {}", path.display(), n, d.msg),
+                None => println!("{}: {}", path.display(), d.msg),
+            }
+            problems += 1;
+        }
+    }
+
+    match problems {
+        0 => Ok(()),
+        1 => Err(BumblingError::InvalidPuzzle("1 problem found".to_string())),
+        n => Err(BumblingError::InvalidPuzzle(format!("{} problems found", n))),
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    match cli.command {
+        Command::Play => cmd_play(&cli),
+        Command::Fetch => cmd_fetch(&cli),
+        Command::Open => run_game_from_file(Path::new(&cli.args[0]), cli.center, cli.frontend),
+        Command::Stats => cmd_stats(&cli),
+        Command::Generate => run_generate(Path::new(&cli.args[0]), cli.output.as_deref(), &cli.gen),
+        Command::Solve => cmd_solve(&cli),
+        Command::Hints => cmd_hints(&cli),
+        Command::Check => cmd_check(&cli),
+        Command::Dates => list_dates(&*puzzle_source(&cli)?, cli.tz),
+        Command::Help => {
+            print!("{}", cli::help_for(cli.args.first().map(|a| a.as_str()))?);
+            Ok(())
+        },
     }
-    eprintln!(concat!("usage: ./bumbling ((", argmar!(), "w|", argmar!(), "s) <url> <path>? | ", argmar!(), "f <path>)*"));
-    std::process::exit(1);
 }
 
 fn main() {
-    let today = format!("{}", Utc::now().with_timezone(&Pacific).format("%Y%m%d"));
-    //println!("{}", today);
     let args: Vec<String> = std::env::args().collect();
-    if args.len() < 2 {
-        let strloc = format!("{}.bumble", today);
-        let path = Path::new(&strloc);
 
-        if path.exists() {
-            run_game_from_file(path);
-        } else {
-            run_game_from_web("https://nytbee.com", path, false);
-        }
+    let config = match config::load() {
+        Ok(c) => c,
+        Err(e) => {
+            exit_on_error(Err(e));
+            unreachable!();
+        },
+    };
 
+    if args.get(1).is_some_and(|a| a.starts_with('_')) {
+        legacy::run(&args, config.timezone);
         return;
     }
 
-    let mut argi = 1;
-    println!("{:?}", args);
-    while argi < args.len() {
-        argi += 1;
-        match args[argi - 1].as_str() {
-            v @ (concat!(argmar!(), "w") | concat!(argmar!(), "s")) => {
-                if argi >= args.len() {
-                    usage(argi);
-                }
-                let url = &args[argi];
-                if url.starts_with(argmar!()) {
-                    usage(argi);
-                }
-                argi += 1;
-
-                let strloc;
-                let path = if argi < args.len() && !args[argi].starts_with(argmar!()) {
-                    argi += 1;
-                    Path::new(&args[argi - 1])
-                } else {
-                    let re = Regex::new(r"Bee_([0-9]{8}).html").unwrap();
-                    if let Some(caps) = re.captures(url) {
-                        strloc = format!("{}.bumble", &caps[1]);
-                        Path::new(&strloc)
-                    } else {
-                        strloc = format!("{}.bumble", today);
-                        Path::new(&strloc)
-                    }
-                };
-
-                run_game_from_web(&url, &path, v.as_bytes()[1] == 's' as u8);
-            },
-            concat!(argmar!(), "f") => {
-                let path = if argi < args.len() && !args[argi].starts_with(argmar!()) {
-                    argi += 1;
-                    Path::new(&args[argi - 1])
-                } else {
-                    usage(argi);
-                    unreachable!();
-                };
-
-                run_game_from_file(path);
-            },
-            _ => {usage(argi - 1);},
-        }
-    }
-
+    exit_on_error(cli::parse(&args[1..], &config).and_then(run));
 }
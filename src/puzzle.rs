@@ -0,0 +1,153 @@
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+
+use crate::error::{BumblingError, Result};
+
+pub fn lset(w: &str) -> u32 {
+    let mut lset: u32 = 0;
+    for c in w.chars() {
+        lset |= 1 << (c as u32 & 31);
+    }
+    lset
+}
+
+// collects the 7 puzzle letters from the answer set and moves every letter that
+// appears in all answers to the front. returns how many center candidates there
+// are.
+pub fn get_letters(words: &[String], letters: &mut [char; 7]) -> Result<usize> {
+    let mut l_part = 0;
+
+    let mut lset_max: u32 = 0;
+
+    'letter_collect: for w in words.iter() {
+        for c in w.chars() {
+            if !c.is_ascii_lowercase() {
+                continue
+            }
+            if lset_max & 1 << (c as u32 & 31) == 0 {
+                lset_max |= 1 << (c as u32 & 31);
+                letters[l_part] = c;
+                l_part += 1;
+
+                if l_part == 7 {
+                    break 'letter_collect;
+                }
+            }
+        }
+    }
+
+    if l_part < 7 {
+        return Err(BumblingError::InvalidPuzzle("word set has less than 7 letters".to_string()));
+    }
+
+    for w in words.iter() {
+        let lset = lset(w);
+
+        if lset & !lset_max != 0 {
+            return Err(BumblingError::InvalidPuzzle(format!("word set has more than 7 letters, {} adds one", w)));
+        }
+
+        let mut i = 0;
+        while i < l_part {
+            if lset & 1 << (letters[i] as u32 & 31) != 0 {
+                i += 1;
+            } else {
+                l_part -= 1;
+                if i != l_part {
+                    letters.swap(i, l_part);
+                }
+            }
+        }
+    }
+
+    if l_part == 0 {
+        return Err(BumblingError::InvalidPuzzle("no possible valid center letter".to_string()));
+    }
+
+    Ok(l_part)
+}
+
+// moves `center` to the front if it is one of the first `candidates` letters
+pub fn pick_center(letters: &mut [char; 7], candidates: usize, center: char) -> Result<()> {
+    match letters.iter().position(|l| *l == center) {
+        Some(i) if i < candidates => {
+            letters.swap(0, i);
+            Ok(())
+        },
+        Some(_) => Err(BumblingError::InvalidPuzzle(format!("center letter {} is missing from some answers", center))),
+        None => Err(BumblingError::InvalidPuzzle(format!("center letter {} is not one of the letters", center))),
+    }
+}
+
+// the puzzle letters with the center first, failing with AmbiguousCenter
+// instead of guessing when the answers allow several centers
+pub fn letters_for(words: &[String], center: Option<char>) -> Result<[char; 7]> {
+    let mut letters: [char; 7] = ['\0'; 7];
+
+    let candidates = get_letters(words, &mut letters)?;
+    match center {
+        Some(c) => pick_center(&mut letters, candidates, c)?,
+        None if candidates > 1 => {
+            return Err(BumblingError::AmbiguousCenter(letters[..candidates].to_vec()));
+        },
+        None => {},
+    }
+
+    Ok(letters)
+}
+
+pub struct Puzzle {
+    // letters[0] is the center letter
+    pub letters: [char; 7],
+    pub answers: Vec<String>,
+    pub date: Option<NaiveDate>,
+    pub source: Option<String>,
+    // zone whose midnight decided `date`
+    pub timezone: Option<Tz>,
+}
+
+impl Puzzle {
+    pub fn new(letters: [char; 7], mut answers: Vec<String>) -> Puzzle {
+        answers.retain(|w| !w.is_empty());
+        answers.sort();
+        answers.dedup();
+        Puzzle { letters, answers, date: None, source: None, timezone: None }
+    }
+
+    pub fn center(&self) -> char {
+        self.letters[0]
+    }
+
+    pub fn lset(&self) -> u32 {
+        let mut lset_max: u32 = 0;
+        for c in self.letters {
+            lset_max |= 1 << (c as u32 & 31);
+        }
+        lset_max
+    }
+
+    pub fn is_answer(&self, w: &str) -> bool {
+        self.answers.binary_search_by(|a| a.as_str().cmp(w)).is_ok()
+    }
+
+    pub fn is_pangram(&self, w: &str) -> bool {
+        lset(w) == self.lset()
+    }
+
+    // a pangram using each letter exactly once
+    pub fn is_perfect_pangram(&self, w: &str) -> bool {
+        w.len() == 7 && self.is_pangram(w)
+    }
+
+    pub fn score(&self, w: &str) -> u32 {
+        if w.len() < 5 {
+            1
+        } else {
+            w.len() as u32 + if self.is_pangram(w) {7} else {0}
+        }
+    }
+
+    pub fn max_score(&self) -> u32 {
+        self.answers.iter().map(|w| self.score(w)).sum()
+    }
+}
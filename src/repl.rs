@@ -0,0 +1,56 @@
+use std::io::BufRead;
+use std::path::Path;
+
+use bumbling::{BumblingError, GameState};
+use bumbling::error::Result;
+use bumbling::game::shuffle_letters;
+use bumbling::hints::{self, Grid};
+use bumbling::save::write_save;
+
+// one line in, one line out, so scripts and screen readers can follow along:
+//   LETTERS tomcinl      center first, after startup and !shuffle
+//   OK toot 1            accepted, with the points
+//   OK motion 13 pangram
+//   ERR not-in-list      rejected, with Submission::key
+//   HINT <line>          the letter grid and two-letter list, after !hints
+fn letters(l: &[char; 7]) -> String {
+    format!("LETTERS {}", l.iter().collect::<String>())
+}
+
+// reads words until !quit or end of input, saving after every accepted word
+pub fn run(state: &mut GameState, path: &Path) -> Result<()> {
+    let mut l = state.puzzle.letters;
+    println!("{}", letters(&l));
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line.map_err(|e| BumblingError::Terminal(e.to_string()))?;
+        let word = line.trim().to_lowercase();
+        match word.as_str() {
+            "" => {},
+            "!quit" => break,
+            "!shuffle" => {
+                shuffle_letters(&mut l);
+                println!("{}", letters(&l));
+            },
+            "!hints" => {
+                state.use_hint("grid");
+                for h in Grid::new(state).lines().into_iter().chain(hints::two_letter_lines(state)) {
+                    println!("HINT {}", h);
+                }
+            },
+            w if w.starts_with('!') => println!("ERR unknown-command"),
+            w => {
+                let outcome = state.submit(w);
+                if outcome.is_ok() {
+                    let pangram = if state.puzzle.is_pangram(w) { " pangram" } else { "" };
+                    println!("OK {} {}{}", w, state.puzzle.score(w), pangram);
+                    write_save(state, path)?;
+                } else {
+                    println!("ERR {}", outcome.key());
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
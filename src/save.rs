@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use chrono_tz::Tz;
+
+use crate::error::{BumblingError, Result};
+use crate::game::{Find, GameState, HintUse};
+use crate::puzzle::Puzzle;
+
+pub const SAVE_VERSION: u32 = 2;
+
+// version 1 is one word per line, found words have a trailing '+'.
+//
+// version 2 adds a header of `#key value` lines before the words and an
+// optional timestamp after the '+':
+//   #bumble 2
+//   #letters olcmtin
+//   #center o
+//   #date 2024-01-01
+//   #timezone America/Los_Angeles
+//   #source https://nytbee.com/Bee_20240101.html
+//   #created 2024-01-01T08:00:00Z
+//   #hint 2024-01-01T08:10:00Z grid
+//   #miss not-in-list 3
+//   cool+ 2024-01-01T08:03:12Z
+//   loco
+pub struct SaveFile {
+    pub version: u32,
+    pub letters: Option<[char; 7]>,
+    pub center: Option<char>,
+    pub date: Option<NaiveDate>,
+    pub timezone: Option<Tz>,
+    pub source: Option<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub hints: Vec<HintUse>,
+    pub misses: BTreeMap<String, u32>,
+    pub words: Vec<String>,
+    pub found: Vec<Find>,
+}
+
+fn header_err(n: usize, msg: impl std::fmt::Display) -> BumblingError {
+    BumblingError::InvalidPuzzle(format!("save line {}: {}", n + 1, msg))
+}
+
+fn parse_time(s: &str) -> std::result::Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| format!("bad time {:?}: {}", s, e))
+}
+
+fn format_time(t: &DateTime<Utc>) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+// one line of a save, a `#key value` header or a word
+fn parse_line(save: &mut SaveFile, line: &str) -> std::result::Result<(), String> {
+    if let Some(h) = line.strip_prefix('#') {
+        let (key, val) = h.split_once(' ').unwrap_or((h, ""));
+        let val = val.trim();
+        match key {
+            "bumble" => {
+                save.version = val.parse::<u32>().map_err(|e| e.to_string())?;
+                if save.version > SAVE_VERSION {
+                    return Err(format!("unsupported save version {}", save.version));
+                }
+            },
+            "letters" => {
+                let letters: Vec<char> = val.chars().collect();
+                save.letters = Some(letters.try_into().map_err(|_| "expected 7 letters".to_string())?);
+            },
+            "center" => save.center = val.chars().next(),
+            "date" => {
+                save.date = Some(NaiveDate::parse_from_str(val, "%Y-%m-%d")
+                                 .map_err(|e| format!("bad date {:?}: {}", val, e))?);
+            },
+            "timezone" => {
+                save.timezone = Some(val.parse().map_err(|_| format!("unknown timezone {}", val))?);
+            },
+            "source" => save.source = Some(val.to_string()),
+            "created" => save.created = Some(parse_time(val)?),
+            "hint" => {
+                let (at, what) = val.split_once(' ').unwrap_or((val, ""));
+                save.hints.push(HintUse { at: parse_time(at)?, what: what.to_string() });
+            },
+            "miss" => {
+                let (kind, count) = val.split_once(' ').unwrap_or((val, ""));
+                let count = count.trim().parse::<u32>().map_err(|e| e.to_string())?;
+                save.misses.insert(kind.to_string(), count);
+            },
+            // unknown keys from newer minor revisions are ignored
+            _ => {},
+        }
+        return Ok(());
+    }
+
+    let (w, at) = line.split_once(' ').unwrap_or((line, ""));
+    let (w, isfound) = match w.strip_suffix('+') {
+        Some(w) => (w, true),
+        None => (w, false),
+    };
+    if w.is_empty() {
+        return Ok(());
+    }
+    if isfound {
+        let at = match at.trim() {
+            "" => None,
+            at => Some(parse_time(at)?),
+        };
+        save.found.push(Find { word: w.to_string(), at });
+    }
+    save.words.push(w.to_string());
+    Ok(())
+}
+
+// what parse would fail with on this line, looked at on its own
+pub fn check_line(line: &str) -> Option<String> {
+    parse_line(&mut SaveFile::new(), line).err()
+}
+
+pub fn parse(src: &str) -> Result<SaveFile> {
+    let mut save = SaveFile::new();
+    for (n, line) in src.split('\n').enumerate() {
+        parse_line(&mut save, line).map_err(|e| header_err(n, e))?;
+    }
+
+    // words are stored alphabetically, recover the order they were found in
+    save.found.sort_by_key(|f| f.at);
+
+    if let (Some(letters), Some(c)) = (save.letters.as_mut(), save.center) {
+        match letters.iter().position(|l| *l == c) {
+            Some(i) => letters.swap(0, i),
+            None => {
+                return Err(BumblingError::InvalidPuzzle(format!("center letter {} is not one of the letters", c)));
+            },
+        }
+    }
+
+    Ok(save)
+}
+
+impl SaveFile {
+    // an empty version 1 save, until a header says otherwise
+    fn new() -> SaveFile {
+        SaveFile {
+            version: 1,
+            letters: None,
+            center: None,
+            date: None,
+            timezone: None,
+            source: None,
+            created: None,
+            hints: Vec::new(),
+            misses: BTreeMap::new(),
+            words: Vec::new(),
+            found: Vec::new(),
+        }
+    }
+
+    // letters[0] is the center letter
+    pub fn into_state(self, letters: [char; 7]) -> GameState {
+        let mut puzzle = Puzzle::new(letters, self.words);
+        puzzle.date = self.date;
+        puzzle.timezone = self.timezone;
+        puzzle.source = self.source;
+
+        let mut state = GameState::new(puzzle);
+        if let Some(created) = self.created {
+            state.created = created;
+        }
+        state.hints = self.hints;
+        state.misses = self.misses;
+        for f in self.found {
+            if state.puzzle.is_answer(&f.word) && !state.is_found(&f.word) {
+                state.found.push(f);
+            }
+        }
+        state
+    }
+}
+
+pub fn serialize(state: &GameState) -> String {
+    let mut res = String::new();
+    let p = &state.puzzle;
+
+    res.push_str(&format!("#bumble {}\n", SAVE_VERSION));
+    res.push_str(&format!("#letters {}\n", p.letters.iter().collect::<String>()));
+    res.push_str(&format!("#center {}\n", p.center()));
+    if let Some(d) = p.date {
+        res.push_str(&format!("#date {}\n", d.format("%Y-%m-%d")));
+    }
+    if let Some(tz) = p.timezone {
+        res.push_str(&format!("#timezone {}\n", tz.name()));
+    }
+    if let Some(ref s) = p.source {
+        res.push_str(&format!("#source {}\n", s));
+    }
+    res.push_str(&format!("#created {}\n", format_time(&state.created)));
+    for h in state.hints.iter() {
+        res.push_str(&format!("#hint {} {}\n", format_time(&h.at), h.what));
+    }
+    for (kind, count) in state.misses.iter() {
+        res.push_str(&format!("#miss {} {}\n", kind, count));
+    }
+
+    for w in p.answers.iter() {
+        res.push_str(w);
+        if let Some(f) = state.found.iter().find(|f| f.word == *w) {
+            res.push('+');
+            if let Some(ref at) = f.at {
+                res.push(' ');
+                res.push_str(&format_time(at));
+            }
+        }
+        res.push('\n');
+    }
+
+    res
+}
+
+pub fn read_save(path: &Path) -> Result<SaveFile> {
+    match std::fs::read_to_string(path) {
+        Ok(src) => parse(&src),
+        Err(e) => Err(BumblingError::SaveIo(path.to_path_buf(), e)),
+    }
+}
+
+pub fn write_save(state: &GameState, path: &Path) -> Result<()> {
+    std::fs::write(path, serialize(state))
+        .map_err(|e| BumblingError::SaveIo(path.to_path_buf(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::letters_for;
+
+    #[test]
+    fn legacy_v1() {
+        let save = parse("clot+\ncolt\nlocomotion+\nmint\nmotion\ntoot\n").unwrap();
+        assert_eq!(save.version, 1);
+        assert!(save.letters.is_none() && save.center.is_none() && save.date.is_none());
+        assert_eq!(save.words, ["clot", "colt", "locomotion", "mint", "motion", "toot"]);
+        let found: Vec<&str> = save.found.iter().map(|f| f.word.as_str()).collect();
+        assert_eq!(found, ["clot", "locomotion"]);
+        assert!(save.found.iter().all(|f| f.at.is_none()));
+
+        let letters = letters_for(&save.words, None).unwrap();
+        let state = save.into_state(letters);
+        assert_eq!(state.puzzle.center(), 't');
+        assert_eq!(state.found.len(), 2);
+    }
+
+    #[test]
+    fn v2_round_trip() {
+        let src = "#bumble 2\n\
+                   #letters olcmtin\n\
+                   #center o\n\
+                   #date 2024-01-01\n\
+                   #timezone America/Los_Angeles\n\
+                   #source https://nytbee.com/Bee_20240101.html\n\
+                   #created 2024-01-01T08:00:00Z\n\
+                   #hint 2024-01-01T08:10:00Z grid\n\
+                   #hint 2024-01-01T08:12:00Z reveal motto 2\n\
+                   #miss not-in-list 3\n\
+                   #miss too-short 1\n\
+                   cool+ 2024-01-01T08:03:12Z\n\
+                   loco\n\
+                   locomotion+ 2024-01-01T08:01:00Z\n\
+                   motto\n";
+        let save = parse(src).unwrap();
+        assert_eq!(save.version, 2);
+        let letters = save.letters.unwrap();
+        let state = save.into_state(letters);
+
+        // found in time order, not file order
+        let found: Vec<&str> = state.found.iter().map(|f| f.word.as_str()).collect();
+        assert_eq!(found, ["locomotion", "cool"]);
+        assert_eq!(state.hints.len(), 2);
+        assert_eq!(state.hints[1].what, "reveal motto 2");
+        assert_eq!(state.misses.get("not-in-list"), Some(&3));
+
+        assert_eq!(serialize(&state), src);
+    }
+
+    #[test]
+    fn newer_version_rejected() {
+        match parse("#bumble 3\n#letters olcmtin\ncool\n") {
+            Err(BumblingError::InvalidPuzzle(msg)) => assert!(msg.contains("unsupported save version 3"), "{}", msg),
+            Err(e) => panic!("wrong error {}", e),
+            Ok(_) => panic!("version 3 accepted"),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+use crate::error::{BumblingError, Result};
+use super::{PuzzleSource, SourcePuzzle};
+
+// a local dump of puzzles, either a single object or an array of them, in the
+// shape of the official game data:
+// {"printDate": "2024-01-01", "centerLetter": "o", "answers": ["..."], "pangrams": ["..."]}
+pub struct JsonDump {
+    path: PathBuf,
+}
+
+fn json_err(path: &Path, e: impl std::fmt::Display) -> BumblingError {
+    BumblingError::HtmlParse(format!("{}: {}", path.display(), e))
+}
+
+fn parse_entry(path: &Path, v: &Value) -> Result<SourcePuzzle> {
+    let date = match v.get("printDate").or_else(|| v.get("date")).and_then(Value::as_str) {
+        Some(d) => Some(NaiveDate::parse_from_str(d, "%Y-%m-%d").map_err(|e| json_err(path, e))?),
+        None => None,
+    };
+
+    let mut words: Vec<String> = Vec::new();
+    // pangrams first, like the nytbee list
+    for key in ["pangrams", "answers"] {
+        if let Some(list) = v.get(key).and_then(Value::as_array) {
+            for w in list.iter().filter_map(Value::as_str) {
+                let w = w.trim().to_lowercase();
+                if !words.contains(&w) {
+                    words.push(w);
+                }
+            }
+        }
+    }
+
+    if words.is_empty() {
+        return Err(json_err(path, "entry has no answers"));
+    }
+
+    let center = v.get("centerLetter").and_then(Value::as_str)
+        .and_then(|c| c.trim().chars().next())
+        .map(|c| c.to_ascii_lowercase());
+
+    Ok(SourcePuzzle { words, center, date })
+}
+
+fn load(path: &Path) -> Result<Vec<SourcePuzzle>> {
+    let src = std::fs::read_to_string(path)
+        .map_err(|e| BumblingError::SaveIo(path.to_path_buf(), e))?;
+    let v: Value = serde_json::from_str(&src).map_err(|e| json_err(path, e))?;
+
+    match v {
+        Value::Array(list) => list.iter().map(|e| parse_entry(path, e)).collect(),
+        v => Ok(vec![parse_entry(path, &v)?]),
+    }
+}
+
+fn latest(path: &Path, entries: Vec<SourcePuzzle>) -> Result<SourcePuzzle> {
+    match entries.into_iter().max_by_key(|e| e.date) {
+        Some(e) => Ok(e),
+        None => Err(json_err(path, "no puzzles in dump")),
+    }
+}
+
+impl JsonDump {
+    pub fn new(path: impl AsRef<Path>) -> JsonDump {
+        JsonDump { path: path.as_ref().to_path_buf() }
+    }
+}
+
+impl PuzzleSource for JsonDump {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn fetch_latest(&self) -> Result<SourcePuzzle> {
+        latest(&self.path, load(&self.path)?)
+    }
+
+    // the url is another dump on disk
+    fn fetch_url(&self, url: &str) -> Result<SourcePuzzle> {
+        let path = Path::new(url.strip_prefix("file://").unwrap_or(url));
+        latest(path, load(path)?)
+    }
+
+    fn fetch_date(&self, date: NaiveDate) -> Result<SourcePuzzle> {
+        match load(&self.path)?.into_iter().find(|e| e.date == Some(date)) {
+            Some(e) => Ok(e),
+            None => Err(BumblingError::InvalidPuzzle(format!("no puzzle for {} in {}", date, self.path.display()))),
+        }
+    }
+
+    fn list_dates(&self, _today: NaiveDate) -> Result<Vec<NaiveDate>> {
+        let mut dates: Vec<NaiveDate> = load(&self.path)?.iter().filter_map(|e| e.date).collect();
+        dates.sort();
+        Ok(dates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(name: &str, src: &str) -> JsonDump {
+        let path = std::env::temp_dir().join(format!("bumbling-{}-{}.json", name, std::process::id()));
+        std::fs::write(&path, src).unwrap();
+        JsonDump::new(path)
+    }
+
+    #[test]
+    fn latest_date() {
+        let d = dump("latest", r#"[
+            {"printDate": "2024-01-01", "centerLetter": "O", "answers": ["clot", "Locomotion"], "pangrams": ["locomotion"]},
+            {"printDate": "2024-01-03", "centerLetter": "t", "answers": ["toot"]},
+            {"printDate": "2024-01-02", "answers": ["motion"]}
+        ]"#);
+
+        let p = d.fetch_latest().unwrap();
+        assert_eq!(p.date, NaiveDate::from_ymd_opt(2024, 1, 3));
+        assert_eq!(p.words, ["toot"]);
+        assert_eq!(p.center, Some('t'));
+
+        let p = d.fetch_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()).unwrap();
+        assert_eq!(p.words, ["locomotion", "clot"]);
+        assert_eq!(p.center, Some('o'));
+
+        assert_eq!(d.list_dates(NaiveDate::from_ymd_opt(2024, 1, 3).unwrap()).unwrap().len(), 3);
+        assert!(d.fetch_date(NaiveDate::from_ymd_opt(2024, 1, 4).unwrap()).is_err());
+        let _ = std::fs::remove_file(&d.path);
+    }
+
+    #[test]
+    fn single_object() {
+        let d = dump("single", r#"{"date": "2024-01-01", "answers": ["clot"]}"#);
+        assert_eq!(d.fetch_latest().unwrap().words, ["clot"]);
+        let _ = std::fs::remove_file(&d.path);
+    }
+
+    #[test]
+    fn malformed_entries() {
+        for (name, src) in [
+            ("noanswers", r#"[{"printDate": "2024-01-01", "answers": []}]"#),
+            ("baddate", r#"[{"printDate": "January 1", "answers": ["clot"]}]"#),
+            ("notjson", r#"[{"printDate": "2024-01-01", "#),
+            ("empty", "[]"),
+        ] {
+            let d = dump(name, src);
+            assert!(matches!(d.fetch_latest(), Err(BumblingError::HtmlParse(_))), "{}", name);
+            let _ = std::fs::remove_file(&d.path);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+
+use crate::error::{BumblingError, Result};
+
+pub mod nytbee;
+mod selector;
+mod json;
+
+pub use nytbee::NytBee;
+pub use selector::SelectorSource;
+pub use json::JsonDump;
+
+pub struct SourcePuzzle {
+    pub words: Vec<String>,
+    // set when the page or dump says which letter is in the middle
+    pub center: Option<char>,
+    // set when the page or dump says which day it is for
+    pub date: Option<NaiveDate>,
+}
+
+pub trait PuzzleSource {
+    fn name(&self) -> &str;
+
+    // today's (or the most recent) puzzle
+    fn fetch_latest(&self) -> Result<SourcePuzzle>;
+
+    fn fetch_url(&self, url: &str) -> Result<SourcePuzzle>;
+
+    fn url_for_date(&self, _date: NaiveDate) -> Option<String> {
+        None
+    }
+
+    fn fetch_date(&self, date: NaiveDate) -> Result<SourcePuzzle> {
+        match self.url_for_date(date) {
+            Some(url) => self.fetch_url(&url),
+            None => Err(BumblingError::Usage(format!("source {} can not fetch by date", self.name()))),
+        }
+    }
+
+    // every date there is a puzzle for. `today` is in the configured zone, for
+    // sources that count up to it
+    fn list_dates(&self, today: NaiveDate) -> Result<Vec<NaiveDate>>;
+}
+
+// nytbee | select:<selector>[;<url template>] | json:<path>
+pub fn from_spec(spec: &str) -> Result<Box<dyn PuzzleSource>> {
+    let (kind, arg) = spec.split_once(':').unwrap_or((spec, ""));
+
+    match kind {
+        "nytbee" => Ok(Box::new(NytBee)),
+        "select" => {
+            let (sel, template) = match arg.split_once(';') {
+                Some((sel, t)) => (sel, Some(t.to_string())),
+                None => (arg, None),
+            };
+            Ok(Box::new(SelectorSource::new(sel, template)?))
+        },
+        "json" => Ok(Box::new(JsonDump::new(arg))),
+        _ => Err(BumblingError::Usage(format!("unknown puzzle source {}", spec))),
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::{Days, NaiveDate};
+use regex::Regex;
+
+use html5ever::parse_document;
+use html5ever::tendril::{TendrilSink, Tendril};
+use rcdom::{Handle, NodeData, RcDom};
+
+use crate::error::{BumblingError, Result};
+use crate::web::fetch_page;
+use super::{PuzzleSource, SourcePuzzle};
+
+const BASE_URL: &str = "https://nytbee.com";
+
+pub struct NytBee;
+
+fn walk(handle: &Handle, words: &mut Vec<String>, mut in_list: bool, mut pangram: bool) {
+    let node = handle;
+
+    match node.data {
+        NodeData::Text { ref contents } if in_list => {
+            let content = contents.borrow();
+            if content.trim() != "" {
+                if pangram {
+                    words.insert(0, content.trim().to_string());
+                } else {
+                    words.push(content.trim().to_string());
+                }
+            }
+        },
+
+        NodeData::Element {
+            ref name,
+            ref attrs,
+            ..
+        } => {
+            // inline svg and mathml hold nothing of ours
+            if name.ns != ns!(html) {
+                return;
+            }
+            if in_list && *name.local == *"strong" {
+                pangram = true;
+            }
+            for attr in attrs.borrow().iter() {
+                if attr.name.ns == ns!() && *attr.name.local == *"id" && attr.value == Tendril::from("main-answer-list") {
+                    in_list = true;
+                }
+            }
+        },
+
+        _ => {},
+    }
+
+    for child in node.children.borrow().iter() {
+        walk(child, words, in_list, pangram);
+    }
+}
+
+pub fn parse_words(html: &str) -> Vec<String> {
+    let dom = parse_document(RcDom::default(), Default::default())
+        .one(html);
+
+    let mut words = Vec::new();
+    walk(&dom.document, &mut words, false, false);
+    words
+}
+
+// the date in an archive page url, `.../Bee_20240101.html`
+pub fn date_from_url(url: &str) -> Option<NaiveDate> {
+    let re = Regex::new(r"Bee_([0-9]{8})\.html").unwrap();
+    re.captures(url)
+        .and_then(|caps| NaiveDate::parse_from_str(&caps[1], "%Y%m%d").ok())
+}
+
+// best effort, the hive is drawn as an image but the center letter is usually
+// also named in the page text or a `center-letter` element
+pub fn find_center(html: &str) -> Option<char> {
+    let re = Regex::new(r"(?i)center[-_ ]letter(?:\s+is)?(?:[^a-z<]|<[^>]*>)*?([a-z])(?:[^a-z]|$)").unwrap();
+    re.captures(html)
+        .and_then(|caps| caps[1].chars().next())
+        .map(|c| c.to_ascii_lowercase())
+}
+
+impl PuzzleSource for NytBee {
+    fn name(&self) -> &str {
+        "nytbee"
+    }
+
+    fn fetch_latest(&self) -> Result<SourcePuzzle> {
+        self.fetch_url(BASE_URL)
+    }
+
+    fn fetch_url(&self, url: &str) -> Result<SourcePuzzle> {
+        let html = fetch_page(url)?;
+        let words = parse_words(&html);
+        if words.is_empty() {
+            return Err(BumblingError::HtmlParse("no answer list found".to_string()));
+        }
+
+        Ok(SourcePuzzle { words, center: find_center(&html), date: date_from_url(url) })
+    }
+
+    fn url_for_date(&self, date: NaiveDate) -> Option<String> {
+        Some(format!("{}/Bee_{}.html", BASE_URL, date.format("%Y%m%d")))
+    }
+
+    // the archive has one page per day since the first puzzle
+    fn list_dates(&self, today: NaiveDate) -> Result<Vec<NaiveDate>> {
+        let first = NaiveDate::from_ymd_opt(2018, 7, 29).unwrap();
+
+        let mut dates = Vec::new();
+        let mut d = first;
+        while d <= today {
+            dates.push(d);
+            d = d + Days::new(1);
+        }
+        Ok(dates)
+    }
+}
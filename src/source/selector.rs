@@ -0,0 +1,200 @@
+use chrono::NaiveDate;
+
+use html5ever::parse_document;
+use html5ever::tendril::TendrilSink;
+use rcdom::{Handle, NodeData, RcDom};
+
+use crate::error::{BumblingError, Result};
+use crate::web::fetch_page;
+use super::{PuzzleSource, SourcePuzzle};
+
+// a single `tag#id.class.class` step of a selector
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+}
+
+// whitespace separated compounds, each one a descendant of the previous, e.g.
+// `#main-answer-list li` or `div.answers ul.column-list li`
+pub struct SelectorSource {
+    selector: String,
+    parts: Vec<Compound>,
+    // url with `{date}` in place of the YYYYMMDD date
+    template: Option<String>,
+}
+
+fn parse_compound(s: &str) -> Result<Compound> {
+    let mut c = Compound { tag: None, id: None, classes: Vec::new() };
+
+    let mut kind = ' ';
+    let mut cur = String::new();
+    for ch in s.chars().chain(std::iter::once('\0')) {
+        if ch == '#' || ch == '.' || ch == '\0' {
+            match kind {
+                ' ' if !cur.is_empty() => c.tag = Some(cur.to_ascii_lowercase()),
+                ' ' => {},
+                '#' if !cur.is_empty() && c.id.is_none() => c.id = Some(cur),
+                '.' if !cur.is_empty() => c.classes.push(cur),
+                _ => {
+                    return Err(BumblingError::Usage(format!("bad selector {}", s)));
+                },
+            }
+            kind = ch;
+            cur = String::new();
+        } else if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+            cur.push(ch);
+        } else {
+            return Err(BumblingError::Usage(format!("unsupported character {:?} in selector {}", ch, s)));
+        }
+    }
+
+    Ok(c)
+}
+
+fn matches(handle: &Handle, c: &Compound) -> bool {
+    if let NodeData::Element { ref name, ref attrs, .. } = handle.data {
+        if let Some(ref tag) = c.tag {
+            if *name.local != **tag {
+                return false;
+            }
+        }
+
+        let mut id_ok = c.id.is_none();
+        let mut classes_found = 0;
+        for attr in attrs.borrow().iter() {
+            if *attr.name.local == *"id" {
+                id_ok |= c.id.as_deref() == Some(&*attr.value);
+            } else if *attr.name.local == *"class" {
+                classes_found = c.classes.iter()
+                    .filter(|cl| attr.value.split_whitespace().any(|v| v == cl.as_str()))
+                    .count();
+            }
+        }
+
+        id_ok && classes_found == c.classes.len()
+    } else {
+        false
+    }
+}
+
+fn text_content(handle: &Handle, res: &mut String) {
+    if let NodeData::Text { ref contents } = handle.data {
+        res.push_str(&contents.borrow());
+    }
+    for child in handle.children.borrow().iter() {
+        text_content(child, res);
+    }
+}
+
+fn walk(handle: &Handle, parts: &[Compound], words: &mut Vec<String>) {
+    let mut parts = parts;
+    if matches(handle, &parts[0]) {
+        if parts.len() == 1 {
+            let mut text = String::new();
+            text_content(handle, &mut text);
+            let w = text.trim().to_lowercase();
+            if !w.is_empty() {
+                words.push(w);
+            }
+            return;
+        }
+        parts = &parts[1..];
+    }
+
+    for child in handle.children.borrow().iter() {
+        walk(child, parts, words);
+    }
+}
+
+impl SelectorSource {
+    pub fn new(selector: &str, template: Option<String>) -> Result<SelectorSource> {
+        let parts = selector.split_whitespace()
+            .map(parse_compound)
+            .collect::<Result<Vec<Compound>>>()?;
+        if parts.is_empty() {
+            return Err(BumblingError::Usage("empty selector".to_string()));
+        }
+
+        Ok(SelectorSource { selector: selector.to_string(), parts, template })
+    }
+
+    pub fn parse_words(&self, html: &str) -> Vec<String> {
+        let dom = parse_document(RcDom::default(), Default::default())
+            .one(html);
+
+        let mut words = Vec::new();
+        walk(&dom.document, &self.parts, &mut words);
+        words
+    }
+}
+
+impl PuzzleSource for SelectorSource {
+    fn name(&self) -> &str {
+        "select"
+    }
+
+    fn fetch_latest(&self) -> Result<SourcePuzzle> {
+        match self.template {
+            Some(ref t) if !t.contains("{date}") => self.fetch_url(t),
+            _ => Err(BumblingError::Usage("select source needs a url".to_string())),
+        }
+    }
+
+    fn fetch_url(&self, url: &str) -> Result<SourcePuzzle> {
+        let words = self.parse_words(&fetch_page(url)?);
+        if words.is_empty() {
+            return Err(BumblingError::HtmlParse(format!("nothing matched {}", self.selector)));
+        }
+
+        Ok(SourcePuzzle { words, center: None, date: None })
+    }
+
+    fn url_for_date(&self, date: NaiveDate) -> Option<String> {
+        match self.template {
+            Some(ref t) if t.contains("{date}") => {
+                Some(t.replace("{date}", &date.format("%Y%m%d").to_string()))
+            },
+            _ => None,
+        }
+    }
+
+    fn list_dates(&self, _today: NaiveDate) -> Result<Vec<NaiveDate>> {
+        Err(BumblingError::Usage("select source can not list dates".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<div id=\"main\" class=\"answers\">\
+                        <ul class=\"column-list big\"><li>Clot</li><li> <b>loco</b>motion </li></ul>\
+                        <ul class=\"other\"><li>nope</li></ul></div>\
+                        <ul class=\"column-list\"><li>outside</li></ul>";
+
+    #[test]
+    fn tag_class_id() {
+        let src = SelectorSource::new("div#main.answers ul.column-list.big li", None).unwrap();
+        assert_eq!(src.parse_words(PAGE), ["clot", "locomotion"]);
+
+        let src = SelectorSource::new("#main li", None).unwrap();
+        assert_eq!(src.parse_words(PAGE), ["clot", "locomotion", "nope"]);
+    }
+
+    #[test]
+    fn no_match() {
+        let src = SelectorSource::new("div#other li", None).unwrap();
+        assert!(src.parse_words(PAGE).is_empty());
+        let src = SelectorSource::new("ul.answers li", None).unwrap();
+        assert!(src.parse_words(PAGE).is_empty());
+    }
+
+    #[test]
+    fn bad_selectors() {
+        assert!(SelectorSource::new("", None).is_err());
+        assert!(SelectorSource::new("div > li", None).is_err());
+        assert!(SelectorSource::new("div#a#b", None).is_err());
+        assert!(SelectorSource::new("li[id]", None).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::game::{GameState, Rank};
+
+// how one saved puzzle went
+pub struct Day {
+    pub date: Option<NaiveDate>,
+    pub found: usize,
+    pub answers: usize,
+    pub score: u32,
+    pub max_score: u32,
+    pub rank: Rank,
+    pub pangrams_found: usize,
+    pub pangrams: usize,
+    pub hints: usize,
+}
+
+impl Day {
+    pub fn new(state: &GameState) -> Day {
+        let p = &state.puzzle;
+        let pangrams: Vec<&String> = p.answers.iter().filter(|w| p.is_pangram(w)).collect();
+        Day {
+            date: p.date,
+            found: state.found.len(),
+            answers: p.answers.len(),
+            score: state.score(),
+            max_score: state.max_score(),
+            rank: state.rank(),
+            pangrams_found: pangrams.iter().filter(|w| state.is_found(w)).count(),
+            pangrams: pangrams.len(),
+            hints: state.hints.len(),
+        }
+    }
+
+    // of the points, like the ranks
+    pub fn percent(&self) -> u32 {
+        (self.score * 100).checked_div(self.max_score).unwrap_or(0)
+    }
+}
+
+// days in a row reaching a rank, the run that ended yesterday and the best run
+// anywhere
+#[derive(Default)]
+pub struct Streak {
+    pub current: usize,
+    pub longest: usize,
+}
+
+// days must be sorted by date. undated ones don't count, and neither do those
+// from `today` on, which are still being played.
+fn streak(days: &[Day], rank: Rank, today: NaiveDate) -> Streak {
+    let mut res = Streak::default();
+    let mut prev: Option<NaiveDate> = None;
+    for d in days.iter() {
+        let date = match d.date {
+            Some(date) if date < today => date,
+            _ => continue,
+        };
+        let follows = prev.and_then(|p| p.succ_opt()) == Some(date);
+        res.current = match (d.rank >= rank, follows) {
+            (false, _) => 0,
+            (true, true) => res.current + 1,
+            (true, false) => 1,
+        };
+        res.longest = res.longest.max(res.current);
+        prev = Some(date);
+    }
+
+    // a run that ended before yesterday is over
+    if prev != today.pred_opt() {
+        res.current = 0;
+    }
+    res
+}
+
+pub struct Summary {
+    pub days: Vec<Day>,
+    pub genius: Streak,
+    pub queen_bee: Streak,
+    pub average_found: f64,
+    pub pangrams_found: usize,
+    pub pangrams: usize,
+    // answers left unfound, with how many puzzles missed them, most first
+    pub missed: Vec<(String, usize)>,
+}
+
+// puzzles from `today` on are still being played, so their unfound words
+// aren't counted as missed
+pub fn summarize(states: &[GameState], today: NaiveDate) -> Summary {
+    let mut days: Vec<Day> = states.iter().map(Day::new).collect();
+    days.sort_by_key(|d| d.date);
+
+    let mut missed: HashMap<&str, usize> = HashMap::new();
+    for s in states.iter().filter(|s| s.puzzle.date.is_none_or(|d| d < today)) {
+        for w in s.puzzle.answers.iter().filter(|w| !s.is_found(w)) {
+            *missed.entry(w).or_insert(0) += 1;
+        }
+    }
+    let mut missed: Vec<(String, usize)> = missed.into_iter().map(|(w, n)| (w.to_string(), n)).collect();
+    // missed more often, then longer, counts as harder
+    missed.sort_by(|(a, an), (b, bn)| bn.cmp(an).then(b.len().cmp(&a.len())).then(a.cmp(b)));
+
+    let average_found = if days.is_empty() {
+        0.0
+    } else {
+        days.iter().map(|d| d.found).sum::<usize>() as f64 / days.len() as f64
+    };
+
+    Summary {
+        genius: streak(&days, Rank::Genius, today),
+        queen_bee: streak(&days, Rank::QueenBee, today),
+        average_found,
+        pangrams_found: days.iter().map(|d| d.pangrams_found).sum(),
+        pangrams: days.iter().map(|d| d.pangrams).sum(),
+        missed,
+        days,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: &str, rank: Rank) -> Day {
+        Day {
+            date: Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()),
+            found: 0,
+            answers: 0,
+            score: 0,
+            max_score: 0,
+            rank,
+            pangrams_found: 0,
+            pangrams: 0,
+            hints: 0,
+        }
+    }
+
+    fn genius(days: &[Day], today: &str) -> (usize, usize) {
+        let s = streak(days, Rank::Genius, NaiveDate::parse_from_str(today, "%Y-%m-%d").unwrap());
+        (s.current, s.longest)
+    }
+
+    #[test]
+    fn today_in_progress() {
+        let days = [
+            day("2024-01-01", Rank::Genius),
+            day("2024-01-02", Rank::QueenBee),
+            day("2024-01-03", Rank::Beginner),
+        ];
+        assert_eq!(genius(&days, "2024-01-03"), (2, 2));
+    }
+
+    #[test]
+    fn old_run_is_not_current() {
+        let days = [
+            day("2024-01-01", Rank::Genius),
+            day("2024-01-02", Rank::Genius),
+            day("2024-01-03", Rank::Genius),
+            day("2024-01-10", Rank::Genius),
+            day("2024-01-11", Rank::Nice),
+        ];
+        assert_eq!(genius(&days, "2024-01-12"), (0, 3));
+        assert_eq!(genius(&days[..4], "2024-01-11"), (1, 3));
+        assert_eq!(genius(&days[..4], "2024-01-20"), (0, 3));
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use sdl2::pixels::Color;
+use sdl2::render::{Texture, TextureCreator};
+use sdl2::ttf::Font;
+use sdl2::video::WindowContext;
+
+use bumbling::error::Result;
+
+use crate::gameloop::sdl_err;
+
+// textures are big at the 128pt the font is loaded at, keep a bounded number
+const MAX_TEXTURES: usize = 192;
+// frames a texture may go unused before it is dropped
+const MAX_IDLE: u64 = 512;
+
+struct Entry<'a> {
+    texture: Texture<'a>,
+    used: u64,
+}
+
+// rendered strings kept as textures between frames, keyed by (text, color),
+// so the found list costs a copy per word instead of a font render
+pub struct TextCache<'a> {
+    font: Font<'a, 'static>,
+    tc: &'a TextureCreator<WindowContext>,
+    entries: HashMap<(String, Color), Entry<'a>>,
+    frame: u64,
+}
+
+impl<'a> TextCache<'a> {
+    pub fn new(font: Font<'a, 'static>, tc: &'a TextureCreator<WindowContext>) -> TextCache<'a> {
+        TextCache { font, tc, entries: HashMap::new(), frame: 0 }
+    }
+
+    pub fn get(&mut self, txt: &str, color: Color) -> Result<&Texture<'a>> {
+        let frame = self.frame;
+        let key = (txt.to_string(), color);
+        if !self.entries.contains_key(&key) {
+            let surf = self.font.render(txt).blended(color).map_err(sdl_err)?;
+            let texture = self.tc.create_texture_from_surface(&surf).map_err(sdl_err)?;
+            self.entries.insert(key.clone(), Entry { texture, used: frame });
+        }
+
+        let entry = self.entries.get_mut(&key).unwrap();
+        entry.used = frame;
+        Ok(&entry.texture)
+    }
+
+    // called once a frame has been presented: drops what hasn't been drawn in
+    // a while, then the least recently drawn if there are still too many
+    pub fn end_frame(&mut self) {
+        let frame = self.frame;
+        self.entries.retain(|_, e| frame - e.used < MAX_IDLE);
+
+        if self.entries.len() > MAX_TEXTURES {
+            let mut ages: Vec<u64> = self.entries.values().map(|e| e.used).collect();
+            ages.sort_unstable();
+            let cutoff = ages[self.entries.len() - MAX_TEXTURES];
+            self.entries.retain(|_, e| e.used >= cutoff);
+        }
+
+        self.frame += 1;
+    }
+}
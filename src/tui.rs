@@ -0,0 +1,286 @@
+use std::io::{ErrorKind, Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use bumbling::{BumblingError, GameState};
+use bumbling::error::Result;
+use bumbling::game::{shuffle_letters, Submission};
+use bumbling::hints::{self, Grid};
+use bumbling::save::write_save;
+
+const RESET: &str = "\x1b[0m";
+const YELLOW: &str = "\x1b[1;33m";
+const RED: &str = "\x1b[31m";
+const DIM: &str = "\x1b[2m";
+const CELL: &str = "\x1b[1;97;48;5;240m";
+const CENTER: &str = "\x1b[1;30;43m";
+
+const HELP: &str = "enter submits, backspace deletes, ^U clears, ? shuffles, + reveals, ! hints, ^C quits";
+
+fn term_err(e: impl std::fmt::Display) -> BumblingError {
+    BumblingError::Terminal(e.to_string())
+}
+
+// set by SIGWINCH, the size is only asked for again after it
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_resize(_: libc::c_int) {
+    RESIZED.store(true, Ordering::Relaxed);
+}
+
+// puts the terminal into unbuffered, unechoed mode on the alternate screen
+// and back the way it was when dropped, whatever way the game ends
+struct RawTerminal {
+    saved: libc::termios,
+}
+
+impl RawTerminal {
+    fn new() -> Result<RawTerminal> {
+        let saved = unsafe {
+            let mut t: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut t) != 0 {
+                return Err(term_err(std::io::Error::last_os_error()));
+            }
+            t
+        };
+
+        let mut raw = saved;
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        // without SA_RESTART, so a resize interrupts the read of the next key
+        // and the screen is redrawn at once
+        unsafe {
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &raw) != 0 {
+                return Err(term_err(std::io::Error::last_os_error()));
+            }
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = on_resize as extern "C" fn(libc::c_int) as libc::sighandler_t;
+            libc::sigaction(libc::SIGWINCH, &action, std::ptr::null_mut());
+        }
+        print!("\x1b[?1049h\x1b[?25l");
+        Ok(RawTerminal { saved })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        unsafe {
+            libc::signal(libc::SIGWINCH, libc::SIG_DFL);
+        }
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = std::io::stdout().flush();
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSAFLUSH, &self.saved);
+        }
+    }
+}
+
+// rows and columns, or a classic 24x80 when the terminal can't tell
+fn term_size() -> (usize, usize) {
+    let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+    let ok = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws) } == 0;
+    match (ws.ws_row as usize, ws.ws_col as usize) {
+        (r, c) if ok && r > 0 && c > 0 => (r, c),
+        _ => (24, 80),
+    }
+}
+
+struct Tui {
+    letters: [char; 7],
+    word: String,
+    outcome: Option<Submission>,
+    show_hints: bool,
+    // rows and columns
+    size: (usize, usize),
+}
+
+fn cell(letters: &[char; 7], i: usize) -> String {
+    let color = if i == 0 { CENTER } else { CELL };
+    format!("{} {} {}", color, letters[i].to_ascii_uppercase(), RESET)
+}
+
+fn render(tui: &Tui, state: &GameState) -> String {
+    let (rows, cols) = tui.size;
+    let mut out = String::from("\x1b[2J\x1b[H");
+    let mut line = |s: &str| {
+        out.push_str(s);
+        out.push('\n');
+    };
+
+    let next = match state.next_rank() {
+        Some((r, pts)) => format!(" - {} to {}", pts, r.name()),
+        None => String::new(),
+    };
+    line(&format!(" {}{}{}  {}/{} points{}", YELLOW, state.rank().name(), RESET,
+                  state.score(), state.max_score(), next));
+    line("");
+
+    // the six outer letters around the center, as in the window
+    let l = &tui.letters;
+    line(&format!("      {} {}", cell(l, 1), cell(l, 2)));
+    line(&format!("    {} {} {}", cell(l, 3), cell(l, 0), cell(l, 4)));
+    line(&format!("      {} {}", cell(l, 5), cell(l, 6)));
+    line("");
+
+    let word: String = tui.word.to_ascii_uppercase();
+    let word = match tui.outcome {
+        Some(o) if !o.is_ok() => format!("{}{}{}", RED, word, RESET),
+        _ => word,
+    };
+    line(&format!(" > {}_", word));
+    let status = match tui.outcome {
+        Some(o @ Submission::Pangram(_)) => format!("{}{}{}", YELLOW, o.message(), RESET),
+        Some(o) if o.is_ok() => o.message(),
+        Some(o) => format!("{}{}{}", RED, o.message(), RESET),
+        None => match hints::current_reveal(state) {
+            Some(r) => format!("{}{}{}", DIM, r.shown(), RESET),
+            None => String::new(),
+        },
+    };
+    line(&format!("   {}", status));
+    line("");
+
+    // whatever room is left goes to the found words, or the hints
+    let room = rows.saturating_sub(11).max(1);
+    if tui.show_hints {
+        let mut lines = Grid::new(state).lines();
+        lines.push(String::new());
+        lines.extend(hints::two_letter_lines(state));
+        for l in lines.iter().take(room) {
+            line(&format!(" {}", l));
+        }
+    } else {
+        line(&format!(" found {}/{}", state.found.len(), state.puzzle.answers.len()));
+        let found: Vec<&String> = state.puzzle.answers.iter().filter(|a| state.is_found(a)).collect();
+        let width = found.iter().map(|w| w.len()).max().unwrap_or(0) + 2;
+        let per_line = (cols.saturating_sub(1) / width.max(1)).max(1);
+        let chunks: Vec<&[&String]> = found.chunks(per_line).collect();
+        for (n, chunk) in chunks.iter().enumerate() {
+            if n + 1 == room && chunks.len() > room {
+                line(&format!(" {}+{} more{}", DIM, found.len() - n * per_line, RESET));
+                break;
+            }
+            let mut s = String::from(" ");
+            for w in chunk.iter() {
+                let pad = " ".repeat(width - w.len());
+                if state.puzzle.is_pangram(w) {
+                    s.push_str(&format!("{}{}{}{}", YELLOW, w, RESET, pad));
+                } else {
+                    s.push_str(&format!("{}{}", w, pad));
+                }
+            }
+            line(&s);
+        }
+    }
+
+    out.push_str(&format!("\x1b[{};1H{}{}{}", rows, DIM, HELP, RESET));
+    out
+}
+
+fn read_byte(input: &mut impl Read) -> std::io::Result<Option<u8>> {
+    let mut b = [0u8; 1];
+    loop {
+        match input.read(&mut b) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(b[0])),
+            Err(e) if e.kind() == ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// the next key, or None at the end of input. arrows and function keys come as
+// escape sequences, which are read whole and dropped so that their last byte
+// isn't taken for a letter. an interrupted read is passed on, the caller
+// redraws after a resize.
+fn read_key(input: &mut impl Read) -> std::io::Result<Option<u8>> {
+    let mut b = [0u8; 1];
+    if input.read(&mut b)? == 0 {
+        return Ok(None);
+    }
+    if b[0] != 0x1b {
+        return Ok(Some(b[0]));
+    }
+
+    match read_byte(input)? {
+        // CSI: parameter and intermediate bytes up to a final byte in @..~
+        Some(b'[') => {
+            while let Some(b) = read_byte(input)? {
+                if (0x40..=0x7e).contains(&b) {
+                    break;
+                }
+            }
+            Ok(Some(0x1b))
+        },
+        // SS3: a single byte, F1-F4 and arrows in application mode
+        Some(b'O') => read_byte(input).map(|_| Some(0x1b)),
+        // alt and a key, taken as just the key
+        other => Ok(other),
+    }
+}
+
+// plays in the terminal until ^C or ^D, saving after every accepted word so a
+// dropped connection loses nothing
+pub fn run(state: &mut GameState, path: &Path) -> Result<()> {
+    let _raw = RawTerminal::new()?;
+    let mut tui = Tui {
+        letters: state.puzzle.letters,
+        word: String::new(),
+        outcome: None,
+        show_hints: false,
+        size: term_size(),
+    };
+    shuffle_letters(&mut tui.letters);
+
+    let mut stdout = std::io::stdout();
+    let mut stdin = std::io::stdin().lock();
+    loop {
+        if RESIZED.swap(false, Ordering::Relaxed) {
+            tui.size = term_size();
+        }
+        write!(stdout, "{}", render(&tui, state)).map_err(term_err)?;
+        stdout.flush().map_err(term_err)?;
+
+        let c = match read_key(&mut stdin) {
+            Ok(Some(b)) => b as char,
+            Ok(None) => break,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(term_err(e)),
+        };
+        tui.outcome = None;
+        match c {
+            // ^C, ^D
+            '\x03' | '\x04' => break,
+            // a stray enter isn't a guess
+            '\r' | '\n' if !tui.word.is_empty() => {
+                let outcome = state.submit(&tui.word);
+                tui.outcome = Some(outcome);
+                if outcome.is_ok() {
+                    tui.word.clear();
+                    write_save(state, path)?;
+                }
+            },
+            // backspace, ^H
+            '\x7f' | '\x08' => {
+                tui.word.pop();
+            },
+            // ^U
+            '\x15' => tui.word.clear(),
+            '?' | '/' => shuffle_letters(&mut tui.letters),
+            '+' => {
+                hints::reveal(state, &tui.word);
+            },
+            '!' => {
+                tui.show_hints = !tui.show_hints;
+                if tui.show_hints {
+                    state.use_hint("grid");
+                }
+            },
+            c if tui.letters.contains(&c.to_ascii_lowercase()) => tui.word.push(c.to_ascii_lowercase()),
+            _ => {},
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use curl::easy::Easy;
+
+use crate::error::{BumblingError, Result};
+
+const MAX_RESPONSE_SIZE: usize = 8 << 20;
+
+// bytes 0x80..0xa0 of windows-1252, everything else matches latin-1
+const CP1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+fn charset(content_type: Option<&str>) -> String {
+    if let Some(ct) = content_type {
+        for param in ct.split(';').skip(1) {
+            if let Some((k, v)) = param.split_once('=') {
+                if k.trim().eq_ignore_ascii_case("charset") {
+                    return v.trim().trim_matches('"').to_ascii_lowercase();
+                }
+            }
+        }
+    }
+    "utf-8".to_string()
+}
+
+pub fn decode_body(body: &[u8], content_type: Option<&str>) -> Result<String> {
+    match charset(content_type).as_str() {
+        // a stray bad byte elsewhere on the page shouldn't lose the answers
+        "utf-8" | "utf8" => Ok(String::from_utf8_lossy(body).into_owned()),
+        "us-ascii" | "ascii" | "iso-8859-1" | "latin1" | "l1" => {
+            Ok(body.iter().map(|b| *b as char).collect())
+        },
+        "windows-1252" | "cp1252" => {
+            Ok(body.iter().map(|b| match b {
+                0x80..=0x9f => CP1252_HIGH[(b - 0x80) as usize],
+                _ => *b as char,
+            }).collect())
+        },
+        cs => Err(BumblingError::HtmlParse(format!("unsupported charset {}", cs))),
+    }
+}
+
+pub fn fetch_page(url: &str) -> Result<String> {
+    let mut body: Vec<u8> = Vec::new();
+    let mut too_large = false;
+
+    let mut curler = Easy::new();
+    curler.url(url)?;
+    let res = {
+        let mut transfer = curler.transfer();
+        transfer.write_function(|data| {
+            if body.len() + data.len() > MAX_RESPONSE_SIZE {
+                too_large = true;
+                return Ok(0);
+            }
+            body.extend_from_slice(data);
+            Ok(data.len())
+        })?;
+        transfer.perform()
+    };
+
+    if too_large {
+        return Err(BumblingError::HtmlParse(format!("response larger than {} bytes", MAX_RESPONSE_SIZE)));
+    }
+    res?;
+
+    // a missing archive page is an error page, not a page without answers
+    let code = curler.response_code()?;
+    if !(200..300).contains(&code) {
+        // CURLE_HTTP_RETURNED_ERROR, what curl itself reports with --fail
+        let mut e = curl::Error::new(22);
+        e.set_extra(format!("{} for {}", code, url));
+        return Err(BumblingError::Network(e));
+    }
+
+    decode_body(&body, curler.content_type()?)
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+fn write_tmp(name: &str, src: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bumbling-check-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, src).unwrap();
+    path
+}
+
+fn check(paths: &[&PathBuf]) -> (Option<i32>, String) {
+    let out = Command::new(env!("CARGO_BIN_EXE_bumbling"))
+        .arg("check")
+        .args(paths)
+        .output()
+        .unwrap();
+    (out.status.code(), String::from_utf8(out.stdout).unwrap())
+}
+
+#[test]
+fn problems_exit_with_invalid_puzzle() {
+    let bad = write_tmp("bad.bumble", "#center o\nClot\n\ncool\ncool\nmint\nlocomotion\n");
+    let good = write_tmp("good.bumble", "#center o\nclot\ncool\nlocomotion\nmotion\n");
+
+    let (code, out) = check(&[&good, &bad]);
+    assert_eq!(code, Some(4));
+    let d = bad.display();
+    assert_eq!(out, format!("{d}:2: uppercase letter 'C' in \"Clot\"\n\
+                             {d}:3: empty line\n\
+                             {d}:5: cool is a duplicate of line 4\n\
+                             {d}:6: mint is missing the center letter o\n"));
+
+    assert_eq!(check(&[&good]), (Some(0), String::new()));
+    let _ = std::fs::remove_dir_all(good.parent().unwrap());
+}
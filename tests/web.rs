@@ -0,0 +1,133 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::time::Duration;
+
+use chrono::NaiveDate;
+
+use bumbling::BumblingError;
+use bumbling::puzzle::letters_for;
+use bumbling::source::{NytBee, PuzzleSource};
+use bumbling::source::nytbee::date_from_url;
+
+const FIXTURE: &str = include_str!("fixtures/nytbee.html");
+
+fn serve_chunked(body: &'static [u8], content_type: &'static str, chunk: usize) -> String {
+    serve("200 OK", body, content_type, chunk)
+}
+
+// serves a single response, writing the body a few bytes at a time so the
+// answer list is spread over many separate reads on the client side
+fn serve(status: &'static str, body: &'static [u8], content_type: &'static str, chunk: usize) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = [0u8; 1024];
+        let _ = stream.read(&mut buf);
+
+        write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+               status, content_type, body.len()).unwrap();
+        for part in body.chunks(chunk) {
+            stream.write_all(part).unwrap();
+            stream.flush().unwrap();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    });
+
+    format!("http://{}/Bee_20240101.html", addr)
+}
+
+#[test]
+fn answer_list_split_across_chunks() {
+    let url = serve_chunked(FIXTURE.as_bytes(), "text/html; charset=utf-8", 7);
+    let words = NytBee.fetch_url(&url).unwrap().words;
+
+    assert_eq!(words, [
+        "locomotion", "clot", "colt", "cool", "cotton", "lotion", "motion",
+        "moot", "motto", "onion", "toil", "tool", "toot",
+    ]);
+}
+
+#[test]
+fn latin1_charset() {
+    let body: &'static [u8] = b"<div id=\"main-answer-list\"><ul><li><strong>tr\xe8s</strong></li><li>clot</li></ul></div>";
+    let url = serve_chunked(body, "text/html; charset=ISO-8859-1", 5);
+    let words = NytBee.fetch_url(&url).unwrap().words;
+
+    assert_eq!(words, ["très", "clot"]);
+}
+
+#[test]
+fn inline_svg_and_mathml() {
+    let body = include_bytes!("fixtures/nytbee_svg.html");
+    let url = serve_chunked(body, "text/html; charset=utf-8", 64);
+    let words = NytBee.fetch_url(&url).unwrap().words;
+
+    assert_eq!(words, ["locomotion", "clot", "toot"]);
+}
+
+#[test]
+fn invalid_utf8_outside_the_answers() {
+    let body: &'static [u8] = b"<p>caf\xe9</p><div id=\"main-answer-list\"><ul><li>clot</li><li>toot</li></ul></div>";
+    let url = serve_chunked(body, "text/html; charset=utf-8", 5);
+    let words = NytBee.fetch_url(&url).unwrap().words;
+
+    assert_eq!(words, ["clot", "toot"]);
+}
+
+// every answer has both o and t, so only the page can tell which is the center
+const CENTER_FIXTURE: &str = include_str!("fixtures/nytbee_center.html");
+
+#[test]
+fn center_named_on_page() {
+    let url = serve_chunked(CENTER_FIXTURE.as_bytes(), "text/html; charset=utf-8", 64);
+    let fetched = NytBee.fetch_url(&url).unwrap();
+    assert_eq!(fetched.center, Some('t'));
+
+    assert!(matches!(letters_for(&fetched.words, None), Err(BumblingError::AmbiguousCenter(_))));
+    assert_eq!(letters_for(&fetched.words, fetched.center).unwrap()[0], 't');
+}
+
+#[test]
+fn center_not_named() {
+    let url = serve_chunked(FIXTURE.as_bytes(), "text/html; charset=utf-8", 64);
+    assert_eq!(NytBee.fetch_url(&url).unwrap().center, None);
+
+    let body: &'static [u8] = b"<p>The center lettering is new.</p><div id=\"main-answer-list\"><ul><li>clot</li></ul></div>";
+    let url = serve_chunked(body, "text/html; charset=utf-8", 64);
+    assert_eq!(NytBee.fetch_url(&url).unwrap().center, None);
+}
+
+// a guess that doesn't fit the answers has to be refused, not used
+#[test]
+fn center_guess_not_in_every_answer() {
+    let body = CENTER_FIXTURE.replace("is <b>T</b>", "is <b>L</b>");
+    let url = serve_chunked(body.leak().as_bytes(), "text/html; charset=utf-8", 64);
+    let fetched = NytBee.fetch_url(&url).unwrap();
+    assert_eq!(fetched.center, Some('l'));
+
+    match letters_for(&fetched.words, fetched.center) {
+        Err(BumblingError::InvalidPuzzle(msg)) => assert!(msg.contains("missing from some answers"), "{}", msg),
+        Err(e) => panic!("wrong error {}", e),
+        Ok(_) => panic!("center l accepted"),
+    }
+}
+
+#[test]
+fn date_from_archive_url() {
+    assert_eq!(date_from_url("https://nytbee.com/Bee_20240101.html"), NaiveDate::from_ymd_opt(2024, 1, 1));
+    assert_eq!(date_from_url("https://nytbee.com/Bee_20240101xhtml"), None);
+    assert_eq!(date_from_url("https://nytbee.com/Bee_20241301.html"), None);
+    assert_eq!(date_from_url("https://nytbee.com/"), None);
+}
+
+#[test]
+fn missing_page() {
+    let url = serve("404 Not Found", b"<p>no such puzzle</p>", "text/html", 64);
+    match NytBee.fetch_url(&url) {
+        Err(e @ BumblingError::Network(_)) => assert!(e.to_string().contains("404"), "{}", e),
+        Err(e) => panic!("wrong error {}", e),
+        Ok(_) => panic!("404 page accepted"),
+    }
+}